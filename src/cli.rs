@@ -34,10 +34,29 @@ pub enum Command {
     Add(AddCommand),
     /// 登録済み worktree を一覧表示
     List(ListCommand),
+    /// 全 worktree の状態（未コミット変更、stash、未 push コミットなど）を診断
+    Status(StatusCommand),
     /// worktree を削除
     Remove(RemoveCommand),
     /// 指定 worktree の絶対パスを出力
     Cd(CdCommand),
+    /// よく使う worktree を最近の利用順（frecency）で一覧表示
+    Recent(RecentCommand),
+    /// worktree 内のパスを出力（`worktree:path` 形式）
+    Path(PathCommand),
+    /// 不要な worktree メタデータと孤立ディレクトリを掃除
+    Prune(PruneCommand),
+    /// マージ済みブランチの worktree をまとめて削除
+    Clean(CleanCommand),
+    /// worktree を移動し、ブランチ名も変更
+    #[command(visible_alias = "rename")]
+    Move(MoveCommand),
+    /// worktree をロック（remove / clean / prune の対象外にする）
+    Lock(LockCommand),
+    /// worktree のロックを解除
+    Unlock(UnlockCommand),
+    /// 強制削除時のバックアップから worktree を復元
+    Restore(RestoreCommand),
     /// シェル統合をプロファイルにインストール
     Init(InitCommand),
     /// シェル初期化スクリプトを出力
@@ -58,34 +77,266 @@ pub struct AddCommand {
     pub track: Option<String>,
 }
 
-#[derive(Args, Debug, Clone, Copy)]
+#[derive(Args, Debug, Clone)]
 pub struct ListCommand {
+    /// JSON 形式で出力（--format json と同じ）
+    #[arg(long = "json")]
+    pub json: bool,
+    /// upstream / ベースブランチとの差分（ahead/behind）と最終コミット情報も表示
+    #[arg(short = 'l', long = "long")]
+    pub long: bool,
+    /// --long で比較するベースブランチ（省略時は defaults.base_branch またはメイン worktree のブランチ）
+    #[arg(long = "base", value_name = "BRANCH", requires = "long")]
+    pub base: Option<String>,
+    /// 各 worktree の git status を実行しない（STATUS / CHANGES を省略して高速化）
+    #[arg(long = "no-status")]
+    pub no_status: bool,
+    /// HEAD と index が変わっていない worktree はキャッシュ済みの status を使う
+    #[arg(long = "cached", conflicts_with = "no_status")]
+    pub cached: bool,
+    /// 条件に合う worktree だけを表示（複数指定で AND。例: status=dirty, branch~feature/*, managed, locked, prunable、先頭の ! で否定）
+    #[arg(long = "filter", value_name = "EXPR")]
+    pub filter: Vec<String>,
+    /// 並び順（name / branch / last-commit / ahead）
+    #[arg(long = "sort", value_enum, value_name = "KEY")]
+    pub sort: Option<ListSort>,
+    /// 表示順を逆にする
+    #[arg(long = "reverse")]
+    pub reverse: bool,
+    /// worktree 名だけを 1 行ずつ出力（スクリプト向け）
+    #[arg(long = "names-only", conflicts_with = "json")]
+    pub names_only: bool,
+    /// 出力形式（table / json / ndjson / csv / tsv）
+    #[arg(
+        long = "format",
+        value_enum,
+        value_name = "FORMAT",
+        conflicts_with_all = ["json", "names_only"]
+    )]
+    pub format: Option<ListFormat>,
+    /// worktree ごとに 1 行出力するテンプレート（例: '{{name}}\t{{branch}}'。フィールドは JSON と同じ名前）
+    #[arg(
+        long = "template",
+        value_name = "TEMPLATE",
+        conflicts_with_all = ["json", "names_only", "format"]
+    )]
+    pub template: Option<String>,
+    /// table / csv / tsv で表示する列（カンマ区切り。例: path,branch,status）
+    #[arg(
+        long = "columns",
+        value_name = "COLUMNS",
+        value_delimiter = ',',
+        conflicts_with_all = ["json", "names_only", "template"]
+    )]
+    pub columns: Vec<String>,
+    /// JSON のスキーマバージョン（1: 従来互換、2: 一意な name・完全な head・相対 path・changes・--long の詳細などを含む。--long 指定時の既定は 2）
+    #[arg(long = "json-version", value_enum, value_name = "N")]
+    pub json_version: Option<JsonVersion>,
+    /// 表の色付け（auto: 端末出力で NO_COLOR が未設定のときのみ）
+    #[arg(
+        long = "color",
+        value_enum,
+        value_name = "WHEN",
+        default_value = "auto"
+    )]
+    pub color: ColorChoice,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    /// 標準出力が端末で NO_COLOR が未設定なら色付け（既定）
+    #[default]
+    Auto,
+    /// 常に色付け
+    Always,
+    /// 色付けしない
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum JsonVersion {
+    /// 従来の形式（既定。フィールドは固定）
+    #[default]
+    #[value(name = "1")]
+    V1,
+    /// 一意な name、完全な head、head_short、相対 path、changes、operation、locked、prunable、is_managed、is_detached、--long の詳細を含む形式
+    #[value(name = "2")]
+    V2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum ListFormat {
+    /// 表形式（既定）
+    Table,
+    /// 整形済み JSON 配列（--json と同じ）
+    Json,
+    /// 1 行 1 オブジェクトの JSON（NDJSON）
+    Ndjson,
+    /// ヘッダー付き CSV
+    Csv,
+    /// ヘッダー付き TSV
+    Tsv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum ListSort {
+    /// 表示名順
+    Name,
+    /// ブランチ名順（detached は最後）
+    Branch,
+    /// 最終コミットが新しい順
+    LastCommit,
+    /// upstream より先行しているコミット数が多い順
+    Ahead,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct StatusCommand {
     /// JSON 形式で出力
     #[arg(long = "json")]
     pub json: bool,
+    /// この日数以上触られていない worktree を警告（0 で無効）
+    #[arg(long = "stale-days", value_name = "DAYS", default_value_t = 30)]
+    pub stale_days: u64,
+    /// ディスク使用量を計算しない（大きな worktree で高速化）
+    #[arg(long = "no-disk")]
+    pub no_disk: bool,
 }
 
 #[derive(Args, Debug, Clone)]
 pub struct RemoveCommand {
-    /// 削除対象の worktree
+    /// 削除対象の worktree またはそのパス（複数指定・`*` / `?` のワイルドカード可、省略時は端末上で対話的に選択）
     #[arg(value_name = "WORKTREE")]
-    pub target: Option<String>,
-    /// 強制削除
-    #[arg(short = 'f', long = "force")]
-    pub force: bool,
+    pub targets: Vec<String>,
+    /// 強制削除（2 回指定でロック中の worktree も削除）
+    #[arg(short = 'f', long = "force", action = ArgAction::Count)]
+    pub force: u8,
     /// 対応ブランチも削除
     #[arg(long = "with-branch")]
     pub with_branch: bool,
     /// ブランチが別の worktree にチェックアウトされていても削除
     #[arg(long = "force-branch")]
     pub force_branch: bool,
+    /// ローカルブランチに加えて upstream のリモートブランチも削除（--with-branch を含む）
+    #[arg(long = "with-remote-branch")]
+    pub with_remote_branch: bool,
+    /// base_dir 外の worktree（メイン以外）も削除対象にする
+    #[arg(long = "include-unmanaged")]
+    pub include_unmanaged: bool,
+    /// シェル統合用: 現在の worktree が対象なら移動先を出力し、削除せずに終了
+    #[arg(long = "print-fallback", hide = true)]
+    pub print_fallback: bool,
+    /// 確認を省略（非対話環境で複数の worktree や未保存の作業がある worktree を削除する場合は必須）
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
 }
 
 #[derive(Args, Debug, Clone)]
 pub struct CdCommand {
-    /// 対象 worktree
+    /// 対象 worktree（`-` で直前の worktree、`worktree:dir` でその中のディレクトリ、省略時は端末上で対話的に選択）
+    #[arg(value_name = "WORKTREE")]
+    pub target: Option<String>,
+    /// 現在の worktree 内のサブディレクトリを移動先でも維持（存在しなければ root）
+    #[arg(short = 's', long = "keep-subdir", overrides_with = "no_keep_subdir")]
+    pub keep_subdir: bool,
+    /// 設定 `cd_keep_subdir` を無視して worktree root に移動
+    #[arg(long = "no-keep-subdir", overrides_with = "keep_subdir")]
+    pub no_keep_subdir: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct PathCommand {
+    /// 対象 worktree と、その中のパス（例: `feature/auth:src/main.rs`）
+    #[arg(value_name = "WORKTREE[:PATH]")]
+    pub target: String,
+    /// カレントディレクトリからの相対パスで出力
+    #[arg(long = "relative")]
+    pub relative: bool,
+}
+
+#[derive(Args, Debug, Clone, Copy)]
+pub struct RecentCommand {
+    /// 表示する件数の上限
+    #[arg(short = 'n', long = "limit", value_name = "N")]
+    pub limit: Option<usize>,
+    /// JSON 形式で出力
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
+#[derive(Args, Debug, Clone, Copy)]
+pub struct PruneCommand {
+    /// 削除せず対象の表示のみ行う
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+    /// 確認なしで孤立ディレクトリを削除
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CleanCommand {
+    /// ベースブランチにマージ済み（または upstream が削除済み）のブランチを対象にする
+    #[arg(long = "merged", required = true)]
+    pub merged: bool,
+    /// 比較対象のベースブランチ（省略時は defaults.base_branch またはメイン worktree のブランチ）
+    #[arg(long = "base", value_name = "BRANCH")]
+    pub base: Option<String>,
+    /// dirty / ロック中の worktree と、upstream 削除済みで未マージのブランチも削除
+    #[arg(short = 'f', long = "force")]
+    pub force: bool,
+    /// 削除せず候補の表示のみ行う
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+    /// 確認なしで削除
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MoveCommand {
+    /// 移動対象の worktree
+    #[arg(value_name = "WORKTREE")]
+    pub target: String,
+    /// 新しい名前（base_dir 配下のパスと新しいブランチ名に使用）
+    #[arg(value_name = "NEW_NAME")]
+    pub new_name: String,
+    /// ブランチ名は変更せずディレクトリのみ移動
+    #[arg(long = "keep-branch")]
+    pub keep_branch: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct LockCommand {
+    /// ロックする worktree
+    #[arg(value_name = "WORKTREE")]
+    pub target: Option<String>,
+    /// ロック理由
+    #[arg(long = "reason", value_name = "TEXT")]
+    pub reason: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct UnlockCommand {
+    /// ロックを解除する worktree
+    #[arg(value_name = "WORKTREE")]
+    pub target: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RestoreCommand {
+    /// 復元する worktree 名またはバックアップ ref（省略時はバックアップ一覧を表示）
     #[arg(value_name = "WORKTREE")]
     pub target: Option<String>,
+    /// 復元するバックアップの作成時刻（省略時は最新）
+    #[arg(long = "at", value_name = "TIMESTAMP")]
+    pub at: Option<String>,
+    /// 復元後にバックアップを削除
+    #[arg(long = "drop")]
+    pub drop: bool,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -30,6 +30,10 @@ impl Config {
     pub fn resolved_base_dir(&self, repo_root: &Path) -> PathBuf {
         self.defaults.resolve_base_dir(repo_root)
     }
+
+    pub fn resolved_fallback_dir(&self, repo_root: &Path) -> PathBuf {
+        self.defaults.resolve_fallback_dir(repo_root)
+    }
 }
 
 fn default_version() -> String {
@@ -40,12 +44,26 @@ fn default_version() -> String {
 pub struct Defaults {
     #[serde(default = "default_base_dir")]
     pub base_dir: PathBuf,
+    /// Branch that other branches are compared against (e.g. by `wtw clean`).
+    /// Falls back to the main worktree's branch when unset.
+    #[serde(default)]
+    pub base_branch: Option<String>,
+    /// Where to continue after the current worktree is removed. Relative
+    /// paths are resolved against the main worktree, which is the default.
+    #[serde(default)]
+    pub fallback_dir: Option<PathBuf>,
+    /// Whether `wtw cd` keeps the current subdirectory by default.
+    #[serde(default)]
+    pub cd_keep_subdir: bool,
 }
 
 impl Default for Defaults {
     fn default() -> Self {
         Self {
             base_dir: default_base_dir(),
+            base_branch: None,
+            fallback_dir: None,
+            cd_keep_subdir: false,
         }
     }
 }
@@ -58,6 +76,14 @@ impl Defaults {
             normalize_fs_path(repo_root).join(&self.base_dir)
         }
     }
+
+    pub fn resolve_fallback_dir(&self, repo_root: &Path) -> PathBuf {
+        match &self.fallback_dir {
+            Some(dir) if dir.is_absolute() => normalize_fs_path(dir),
+            Some(dir) => normalize_fs_path(repo_root).join(dir),
+            None => normalize_fs_path(repo_root),
+        }
+    }
 }
 
 fn default_base_dir() -> PathBuf {
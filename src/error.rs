@@ -43,6 +43,17 @@ impl AppError {
     pub fn internal_from(error: impl fmt::Display) -> Self {
         AppError::Internal(error.to_string())
     }
+
+    /// Same category (and therefore exit code) with a different message.
+    pub fn with_message(&self, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match self {
+            AppError::User(_) => AppError::User(message),
+            AppError::Config(_) => AppError::Config(message),
+            AppError::Git(_) => AppError::Git(message),
+            AppError::Internal(_) => AppError::Internal(message),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 pub mod rev;
 pub mod runner;
+pub mod status;
 pub mod worktree;
 
 pub use runner::GitRunner;
@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::git::runner::GitRunner;
+
+/// Working-tree state of one worktree, from `git status --porcelain=v2`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreeStatus {
+    pub changes: ChangeCounts,
+    pub operation: Option<Operation>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChangeCounts {
+    /// Entries with changes in the index.
+    pub staged: usize,
+    /// Tracked entries with changes in the working tree.
+    pub modified: usize,
+    pub untracked: usize,
+    /// Unmerged entries.
+    pub conflicted: usize,
+}
+
+/// A multi-step Git operation that has been started but not finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Operation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl Operation {
+    /// Lower-case name, as serialized (`cherry-pick`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Merge => "merge",
+            Operation::Rebase => "rebase",
+            Operation::CherryPick => "cherry-pick",
+            Operation::Revert => "revert",
+            Operation::Bisect => "bisect",
+        }
+    }
+
+    /// Upper-case label in the style of Git's own prompt (`REBASING`).
+    pub fn label(self) -> &'static str {
+        match self {
+            Operation::Merge => "MERGING",
+            Operation::Rebase => "REBASING",
+            Operation::CherryPick => "CHERRY-PICKING",
+            Operation::Revert => "REVERTING",
+            Operation::Bisect => "BISECTING",
+        }
+    }
+}
+
+impl ChangeCounts {
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl WorktreeStatus {
+    /// Compact summary such as `+1 !2 ?3 =1 REBASING`, or `-` when there is
+    /// nothing to report.
+    pub fn symbols(&self) -> String {
+        let counts = [
+            ('+', self.changes.staged),
+            ('!', self.changes.modified),
+            ('?', self.changes.untracked),
+            ('=', self.changes.conflicted),
+        ];
+        let mut parts: Vec<String> = counts
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(symbol, count)| format!("{}{}", symbol, count))
+            .collect();
+        if let Some(operation) = self.operation {
+            parts.push(operation.label().to_string());
+        }
+        if parts.is_empty() {
+            "-".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+pub fn read_status(git: &GitRunner, worktree_path: &Path) -> Result<WorktreeStatus> {
+    let output = git
+        .run_in(worktree_path, ["status", "--porcelain=v2"])
+        .map_err(|err| AppError::git(err.to_string()))?;
+
+    Ok(WorktreeStatus {
+        changes: parse_porcelain_v2(output.stdout()),
+        operation: git_dir(worktree_path).and_then(|dir| detect_operation(&dir)),
+    })
+}
+
+fn parse_porcelain_v2(output: &str) -> ChangeCounts {
+    let mut counts = ChangeCounts::default();
+    for line in output.lines() {
+        let mut fields = line.split(' ');
+        match fields.next() {
+            Some("1") | Some("2") => {
+                let xy = fields.next().unwrap_or("..").as_bytes();
+                if xy.first().is_some_and(|&x| x != b'.') {
+                    counts.staged += 1;
+                }
+                if xy.get(1).is_some_and(|&y| y != b'.') {
+                    counts.modified += 1;
+                }
+            }
+            Some("u") => counts.conflicted += 1,
+            Some("?") => counts.untracked += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+/// The worktree's own Git directory: `.git` itself in the main worktree, the
+/// `gitdir:` target of the `.git` file in a linked one.
+pub(crate) fn git_dir(worktree_path: &Path) -> Option<PathBuf> {
+    let dot_git = worktree_path.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let contents = fs::read_to_string(&dot_git).ok()?;
+    let target = contents.trim().strip_prefix("gitdir:")?.trim();
+    Some(worktree_path.join(target))
+}
+
+fn detect_operation(git_dir: &Path) -> Option<Operation> {
+    let markers = [
+        ("rebase-merge", Operation::Rebase),
+        ("rebase-apply", Operation::Rebase),
+        ("MERGE_HEAD", Operation::Merge),
+        ("CHERRY_PICK_HEAD", Operation::CherryPick),
+        ("REVERT_HEAD", Operation::Revert),
+        ("BISECT_LOG", Operation::Bisect),
+    ];
+    markers
+        .iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, operation)| *operation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_porcelain_v2_entries() {
+        let output = "\
+1 M. N... 100644 100644 100644 aaa bbb staged.txt
+1 .M N... 100644 100644 100644 aaa aaa modified.txt
+1 MM N... 100644 100644 100644 aaa bbb both.txt
+2 R. N... 100644 100644 100644 aaa aaa R100 new.txt\told.txt
+u UU N... 100644 100644 100644 100644 aaa bbb ccc conflict.txt
+? untracked.txt
+? other.txt
+";
+        let counts = parse_porcelain_v2(output);
+        assert_eq!(
+            counts,
+            ChangeCounts {
+                staged: 3,
+                modified: 2,
+                untracked: 2,
+                conflicted: 1,
+            }
+        );
+        assert!(parse_porcelain_v2("").is_clean());
+    }
+
+    #[test]
+    fn symbols_summarize_counts_and_operation() {
+        let status = WorktreeStatus {
+            changes: ChangeCounts {
+                staged: 1,
+                modified: 0,
+                untracked: 3,
+                conflicted: 2,
+            },
+            operation: Some(Operation::CherryPick),
+        };
+        assert_eq!(status.symbols(), "+1 ?3 =2 CHERRY-PICKING");
+        assert_eq!(WorktreeStatus::default().symbols(), "-");
+    }
+
+    #[test]
+    fn detects_operation_from_linked_worktree_git_dir() {
+        let temp = TempDir::new().unwrap();
+        let admin = temp.path().join("repo.git").join("worktrees").join("wt");
+        let worktree = temp.path().join("wt");
+        fs::create_dir_all(admin.join("rebase-merge")).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", admin.display()),
+        )
+        .unwrap();
+
+        let dir = git_dir(&worktree).unwrap();
+        assert_eq!(detect_operation(&dir), Some(Operation::Rebase));
+        assert_eq!(detect_operation(temp.path()), None);
+    }
+}
@@ -33,9 +33,33 @@ pub fn run() -> Result<ExitCode> {
                 &repo,
                 &git,
                 &config,
-                worktree::list::ListOptions { json: cmd.json },
+                worktree::list::ListOptions {
+                    format: if cmd.json {
+                        cli::ListFormat::Json
+                    } else {
+                        cmd.format.unwrap_or(cli::ListFormat::Table)
+                    },
+                    template: cmd.template.clone(),
+                    columns: cmd.columns.clone(),
+                    json_version: cmd.json_version,
+                    long: cmd.long,
+                    base: cmd.base.clone(),
+                    no_status: cmd.no_status,
+                    cached: cmd.cached,
+                    filters: cmd.filter.clone(),
+                    sort: cmd.sort,
+                    reverse: cmd.reverse,
+                    names_only: cmd.names_only,
+                    color: cmd.color,
+                },
             )?;
         }
+        cli::Command::Status(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::status::run(&repo, &git, &config, &cmd)?;
+        }
         cli::Command::Remove(cmd) => {
             let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
             let config = config::load_config(&repo)?;
@@ -46,7 +70,55 @@ pub fn run() -> Result<ExitCode> {
             let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
             let config = config::load_config(&repo)?;
             let git = git::GitRunner::new(repo.clone());
-            worktree::resolve::run(&repo, &git, &config, cmd.target)?;
+            worktree::resolve::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Recent(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::history::recent(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Path(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::resolve::path(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Prune(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::prune::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Clean(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::clean::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Move(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::relocate::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Lock(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::lock::lock(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Unlock(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::lock::unlock(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Restore(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::backup::restore(&repo, &git, &config, &cmd)?;
         }
         cli::Command::Init(cmd) => match cmd.shell {
             cli::ShellKind::Pwsh => {
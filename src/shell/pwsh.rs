@@ -22,9 +22,29 @@ function wtw {
     )
 
     $exe = Get-WtwExePath
+    $previous = $null
+
+    # 現在の worktree を削除する場合は、削除前に移動先へ移動しておく（使用中のディレクトリは削除できないため）
+    if ($Args.Count -gt 0 -and $Args[0] -eq 'remove') {
+        $rest = @($Args | Select-Object -Skip 1)
+        $fallback = & $exe remove --print-fallback @rest 2>$null
+        if ($LASTEXITCODE -eq 0 -and $fallback) {
+            $fallback = ($fallback | Select-Object -Last 1).Trim()
+            if (Test-Path -LiteralPath $fallback -PathType Container) {
+                $previous = Get-Location
+                Set-Location -LiteralPath $fallback
+            }
+        }
+    }
+
     $output = & $exe @Args
     $exitCode = $LASTEXITCODE
 
+    # 元の場所が残っていれば戻る（失敗時や確認で中止した場合など）
+    if ($previous -and (Test-Path -LiteralPath $previous.Path)) {
+        Set-Location -LiteralPath $previous.Path
+    }
+
     if ($exitCode -eq 0 -and $Args.Count -gt 0 -and $Args[0] -eq 'cd') {
         $destination = ($output | Select-Object -Last 1).Trim()
         if ($destination) {
@@ -42,7 +62,7 @@ function wtw {
 Register-ArgumentCompleter -Native -CommandName wtw -ScriptBlock {
     param($commandName, $parameterName, $wordToComplete, $commandAst, $fakeBoundParameters)
 
-    $commands = @('add','list','remove','cd','shell-init')
+    $commands = @('add','list','status','remove','cd','prune','clean','move','rename','lock','unlock','restore','recent','path','shell-init')
     $elements = @($commandAst.CommandElements | ForEach-Object { $_.Extent.Text })
 
     if ($elements.Count -lt 2) {
@@ -58,7 +78,7 @@ Register-ArgumentCompleter -Native -CommandName wtw -ScriptBlock {
 
     if ($subcommand -eq 'cd') {
         $exe = Get-WtwExePath
-        $json = & $exe list --json 2>$null
+        $json = & $exe list --json --json-version 2 --cached 2>$null
         if (-not $?) {
             return
         }
@@ -75,8 +95,13 @@ Register-ArgumentCompleter -Native -CommandName wtw -ScriptBlock {
                 $displayName = $name
             }
 
+            $toolTip = $displayName
+            if ($item.status) {
+                $toolTip = "$displayName ($($item.status))"
+            }
+
             if ($displayName -like "$wordToComplete*") {
-                [System.Management.Automation.CompletionResult]::new($displayName, $displayName, 'ParameterValue', $displayName)
+                [System.Management.Automation.CompletionResult]::new($displayName, $displayName, 'ParameterValue', $toolTip)
             }
         }
         return
@@ -106,4 +131,14 @@ mod tests {
         assert!(script.contains("function wtw"));
         assert!(script.contains("Register-ArgumentCompleter"));
     }
+
+    #[test]
+    fn script_leaves_current_worktree_before_remove() {
+        let script = script();
+        assert!(script.contains("remove --print-fallback"));
+        assert!(script.contains("Set-Location -LiteralPath $fallback"));
+        // An aborted removal exits 0, so the way back must not depend on
+        // the exit code.
+        assert!(script.contains("if ($previous -and (Test-Path -LiteralPath $previous.Path))"));
+    }
 }
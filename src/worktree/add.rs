@@ -13,7 +13,7 @@ use crate::git::rev::RepoContext;
 use crate::git::runner::{GitError, GitRunner};
 use crate::git::worktree::{WorktreeInfo, list_worktrees};
 use crate::hooks::executor::HookExecutor;
-use crate::worktree::common;
+use crate::worktree::{common, status_cache};
 
 pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &AddCommand) -> Result<()> {
     let existing = list_worktrees(git)?;
@@ -21,6 +21,7 @@ pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &AddComman
 
     ensure_parents_exist(&spec.path)?;
     run_git_add(git, &spec)?;
+    status_cache::invalidate(repo);
 
     let mut stdout = io::stdout().lock();
     let display_path = common::normalize_path(&spec.path);
@@ -130,7 +131,7 @@ fn infer_branch_from_track(track: &str) -> Option<String> {
         .filter(|branch| !branch.is_empty())
 }
 
-fn branch_to_relative_path(name: &str) -> PathBuf {
+pub(crate) fn branch_to_relative_path(name: &str) -> PathBuf {
     let mut result = PathBuf::new();
     for segment in name.split(['/', '\\']) {
         let sanitized = sanitize_segment(segment);
@@ -159,19 +160,22 @@ fn sanitize_segment(segment: &str) -> String {
         .collect()
 }
 
-fn detect_conflicts(path: &Path, branch: Option<&str>, existing: &[WorktreeInfo]) -> Result<()> {
-    if let Some(branch_name) = branch {
-        if let Some(conflict) = existing
+pub(crate) fn detect_conflicts(
+    path: &Path,
+    branch: Option<&str>,
+    existing: &[WorktreeInfo],
+) -> Result<()> {
+    if let Some(branch_name) = branch
+        && let Some(conflict) = existing
             .iter()
             .find(|wt| wt.branch.as_deref() == Some(branch_name))
-        {
-            return Err(AppError::user(format!(
-                "worktree for branch '{}' already exists: {}",
-                branch_name,
-                conflict.path.display()
-            ))
-            .into());
-        }
+    {
+        return Err(AppError::user(format!(
+            "worktree for branch '{}' already exists: {}",
+            branch_name,
+            conflict.path.display()
+        ))
+        .into());
     }
 
     let target_normalized = common::normalize_path(path);
@@ -199,7 +203,7 @@ fn detect_conflicts(path: &Path, branch: Option<&str>, existing: &[WorktreeInfo]
     Ok(())
 }
 
-fn ensure_parents_exist(path: &Path) -> Result<()> {
+pub(crate) fn ensure_parents_exist(path: &Path) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -0,0 +1,641 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+
+use crate::cli::RestoreCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::{GitError, GitOutput, GitRunner};
+use crate::git::worktree::list_worktrees;
+use crate::worktree::{add, common};
+
+const REF_PREFIX: &str = "refs/wtw/backup/";
+
+/// A snapshot of a worktree's uncommitted work, taken before a forced removal.
+#[derive(Debug)]
+pub(crate) struct Backup {
+    /// The worktree's name as it appears in the backup ref (see [`ref_key`]).
+    pub name: String,
+    pub reference: String,
+    pub archive: Option<PathBuf>,
+}
+
+/// Snapshot tracked changes into a commit on `refs/wtw/backup/<key>/<timestamp>`
+/// and archive untracked (non-ignored) files next to it under the git common
+/// directory. `<key>` is `name` made safe for a ref name, see [`ref_key`].
+pub(crate) fn create(
+    repo: &RepoContext,
+    git: &GitRunner,
+    name: &str,
+    path: &Path,
+    branch: Option<&str>,
+) -> std::result::Result<Backup, AppError> {
+    let head = run_git_in(git, path, ["rev-parse", "--verify", "HEAD"], "rev-parse")?
+        .stdout()
+        .trim()
+        .to_string();
+
+    let message = format!(
+        "wtw backup of {}\n\nbranch: {}\npath: {}\n",
+        name,
+        branch.unwrap_or(""),
+        common::normalize_path(path).display()
+    );
+
+    // `git stash create` records index and working tree without touching
+    // either, but prints nothing when only untracked files changed.
+    let stash = run_git_in(
+        git,
+        path,
+        ["stash", "create", message.as_str()],
+        "stash create",
+    )?
+    .stdout()
+    .trim()
+    .to_string();
+    let commit = if stash.is_empty() {
+        let tree = format!("{}^{{tree}}", head);
+        run_git_in(
+            git,
+            path,
+            [
+                "commit-tree",
+                tree.as_str(),
+                "-p",
+                head.as_str(),
+                "-m",
+                message.as_str(),
+            ],
+            "commit-tree",
+        )?
+        .stdout()
+        .trim()
+        .to_string()
+    } else {
+        stash
+    };
+
+    let key = ref_key(name);
+    // Timestamps have one-second resolution; a second backup of the same
+    // name within that second gets a `-2`, `-3`, ... suffix.
+    let now = utc_timestamp(SystemTime::now());
+    let mut timestamp = now.clone();
+    let mut reference = format!("{}{}/{}", REF_PREFIX, key, timestamp);
+    check_ref_format(git, name, &reference)?;
+    let mut attempt = 1;
+    while ref_exists(git, &reference)? {
+        attempt += 1;
+        timestamp = format!("{}-{}", now, attempt);
+        reference = format!("{}{}/{}", REF_PREFIX, key, timestamp);
+    }
+    // The empty old value makes Git refuse to overwrite a ref created since.
+    run_git_in(
+        git,
+        path,
+        ["update-ref", reference.as_str(), commit.as_str(), ""],
+        "update-ref",
+    )?;
+
+    let untracked = run_git_in(
+        git,
+        path,
+        ["ls-files", "--others", "--exclude-standard", "-z"],
+        "ls-files",
+    )?;
+    let files: Vec<&str> = untracked
+        .stdout()
+        .split('\0')
+        .filter(|file| !file.is_empty())
+        .collect();
+
+    let archive = if files.is_empty() {
+        None
+    } else {
+        let archive = archive_path(repo, &key, &timestamp);
+        write_archive(&archive, path, &files).map_err(|err| {
+            AppError::internal(format!(
+                "failed to archive untracked files to {}: {}",
+                archive.display(),
+                err
+            ))
+        })?;
+        Some(archive)
+    };
+
+    Ok(Backup {
+        name: key,
+        reference,
+        archive,
+    })
+}
+
+/// The worktree part of a backup ref: the components of a display name
+/// joined with `/` whatever the platform separator, with anything
+/// `git check-ref-format` rejects replaced.
+fn ref_key(name: &str) -> String {
+    name.split(['/', '\\'])
+        .map(sanitize_ref_component)
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn sanitize_ref_component(component: &str) -> String {
+    let mut sanitized: String = component
+        .chars()
+        .map(|ch| {
+            if ch.is_control() || matches!(ch, ' ' | '~' | '^' | ':' | '?' | '*' | '[' | '\\') {
+                '-'
+            } else {
+                ch
+            }
+        })
+        .collect();
+    while sanitized.contains("..") {
+        sanitized = sanitized.replace("..", ".");
+    }
+    let mut sanitized = sanitized.replace("@{", "@-").trim_matches('.').to_string();
+    if let Some(stem) = sanitized.strip_suffix(".lock") {
+        sanitized = format!("{}-lock", stem);
+    }
+    sanitized
+}
+
+fn check_ref_format(
+    git: &GitRunner,
+    name: &str,
+    reference: &str,
+) -> std::result::Result<(), AppError> {
+    let output = git
+        .run_with_status(["check-ref-format", reference])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(AppError::internal(format!(
+            "cannot back up '{}': {} is not a valid ref name",
+            name, reference
+        )))
+    }
+}
+
+/// Tell the user where the backup went and how to get it back.
+pub(crate) fn report<W: Write>(writer: &mut W, backup: &Backup) -> io::Result<()> {
+    writeln!(
+        writer,
+        "Backed up uncommitted changes to {}",
+        backup.reference
+    )?;
+    if let Some(archive) = &backup.archive {
+        writeln!(writer, "Archived untracked files to {}", archive.display())?;
+    }
+    writeln!(writer, "Restore with: wtw restore {}", backup.name)
+}
+
+pub fn restore(
+    repo: &RepoContext,
+    git: &GitRunner,
+    _config: &Config,
+    cmd: &RestoreCommand,
+) -> Result<()> {
+    let backups = list_backups(git)?;
+    let mut stdout = io::stdout().lock();
+
+    let target = cmd
+        .target
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let Some(target) = target else {
+        output_backups(&mut stdout, &backups)?;
+        return Ok(());
+    };
+
+    let entry = select_backup(&backups, target, cmd.at.as_deref())?;
+    let metadata = read_metadata(git, &entry.reference)?;
+    let path = metadata.path.ok_or_else(|| {
+        AppError::user(format!(
+            "backup {} does not record a worktree path",
+            entry.reference
+        ))
+    })?;
+    if path.exists() {
+        return Err(AppError::user(format!(
+            "cannot restore '{}': {} already exists",
+            entry.name,
+            path.display()
+        ))
+        .into());
+    }
+
+    let base = format!("{}^1", entry.reference);
+    let mut args: Vec<OsString> = vec!["worktree".into(), "add".into()];
+    match &metadata.branch {
+        Some(branch) => {
+            let worktrees = list_worktrees(git)?;
+            if let Some(other) = worktrees
+                .iter()
+                .find(|info| info.branch.as_deref() == Some(branch.as_str()))
+            {
+                return Err(AppError::user(format!(
+                    "cannot restore '{}': branch '{}' is checked out at {}",
+                    entry.name,
+                    branch,
+                    other.path.display()
+                ))
+                .into());
+            }
+            if branch_exists(git, branch)? {
+                args.push(path.as_os_str().to_owned());
+                args.push(branch.into());
+            } else {
+                args.push("-b".into());
+                args.push(branch.into());
+                args.push(path.as_os_str().to_owned());
+                args.push(base.into());
+            }
+        }
+        None => {
+            args.push("--detach".into());
+            args.push(path.as_os_str().to_owned());
+            args.push(base.into());
+        }
+    }
+
+    add::ensure_parents_exist(&path)?;
+    run_git(git, args, "worktree add")?;
+
+    // Only stash-shaped snapshots (HEAD + index parents) carry tracked changes.
+    if metadata.parents > 1 {
+        run_git_in(
+            git,
+            &path,
+            ["stash", "apply", "--index", entry.reference.as_str()],
+            "stash apply",
+        )
+        .map_err(|err| {
+            err.with_message(format!(
+                "recreated worktree at {} but could not apply {}: {}",
+                path.display(),
+                entry.reference,
+                err
+            ))
+        })?;
+    }
+
+    let archive = archive_path(repo, &entry.name, &entry.timestamp);
+    if archive.is_file() {
+        File::open(&archive)
+            .and_then(|file| tar::Archive::new(file).unpack(&path))
+            .map_err(|err| {
+                AppError::internal(format!("failed to extract {}: {}", archive.display(), err))
+            })?;
+    }
+
+    writeln!(
+        stdout,
+        "Restored worktree '{}' at {} from {}",
+        entry.name,
+        common::normalize_path(&path).display(),
+        entry.reference
+    )?;
+
+    if cmd.drop {
+        run_git(
+            git,
+            ["update-ref", "-d", entry.reference.as_str()],
+            "update-ref",
+        )?;
+        if archive.is_file() {
+            fs::remove_file(&archive)?;
+        }
+        writeln!(stdout, "Dropped backup {}", entry.reference)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BackupEntry {
+    name: String,
+    timestamp: String,
+    reference: String,
+}
+
+#[derive(Debug, Default)]
+struct Metadata {
+    branch: Option<String>,
+    path: Option<PathBuf>,
+    parents: usize,
+}
+
+fn list_backups(git: &GitRunner) -> std::result::Result<Vec<BackupEntry>, AppError> {
+    let output = run_git(
+        git,
+        ["for-each-ref", "--format=%(refname)", REF_PREFIX],
+        "for-each-ref",
+    )?;
+    let mut entries: Vec<BackupEntry> = output
+        .stdout()
+        .lines()
+        .filter_map(parse_backup_ref)
+        .collect();
+    entries.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| timestamp_order(&a.timestamp).cmp(&timestamp_order(&b.timestamp)))
+    });
+    Ok(entries)
+}
+
+/// Sort key for a backup timestamp: the time, then the numeric suffix that
+/// tells apart backups taken within the same second (none sorts first).
+fn timestamp_order(timestamp: &str) -> (&str, u32) {
+    match timestamp.split_once('-') {
+        Some((time, suffix)) => (time, suffix.parse().unwrap_or(0)),
+        None => (timestamp, 0),
+    }
+}
+
+fn parse_backup_ref(reference: &str) -> Option<BackupEntry> {
+    let rest = reference.trim().strip_prefix(REF_PREFIX)?;
+    let (name, timestamp) = rest.rsplit_once('/')?;
+    if name.is_empty() || timestamp.is_empty() {
+        return None;
+    }
+    Some(BackupEntry {
+        name: name.to_string(),
+        timestamp: timestamp.to_string(),
+        reference: reference.trim().to_string(),
+    })
+}
+
+/// Pick the newest backup for `target` (a worktree name or a full backup
+/// ref), or the one taken at `at`.
+fn select_backup<'a>(
+    backups: &'a [BackupEntry],
+    target: &str,
+    at: Option<&str>,
+) -> std::result::Result<&'a BackupEntry, AppError> {
+    // `backups` is sorted by timestamp within each name.
+    let key = ref_key(target);
+    let found = backups.iter().rev().find(|entry| {
+        (entry.reference == target || entry.name == key)
+            && at.is_none_or(|at| entry.timestamp == at)
+    });
+
+    found.ok_or_else(|| {
+        let what = match at {
+            Some(at) => format!("backup of '{}' at {}", target, at),
+            None => format!("backup of '{}'", target),
+        };
+        AppError::user(format!(
+            "{} not found\nRun 'wtw restore' to see available backups.",
+            what
+        ))
+    })
+}
+
+fn read_metadata(git: &GitRunner, reference: &str) -> std::result::Result<Metadata, AppError> {
+    let output = run_git(git, ["show", "-s", "--format=%P%n%B", reference], "show")?;
+    let mut lines = output.stdout().lines();
+    let mut metadata = Metadata {
+        parents: lines
+            .next()
+            .map(|parents| parents.split_whitespace().count())
+            .unwrap_or(0),
+        ..Metadata::default()
+    };
+
+    for line in lines {
+        if let Some(branch) = line.strip_prefix("branch: ") {
+            let branch = branch.trim();
+            if !branch.is_empty() {
+                metadata.branch = Some(branch.to_string());
+            }
+        } else if let Some(path) = line.strip_prefix("path: ") {
+            metadata.path = Some(PathBuf::from(path.trim()));
+        }
+    }
+
+    Ok(metadata)
+}
+
+fn branch_exists(git: &GitRunner, branch: &str) -> std::result::Result<bool, AppError> {
+    ref_exists(git, &format!("refs/heads/{}", branch))
+}
+
+fn ref_exists(git: &GitRunner, reference: &str) -> std::result::Result<bool, AppError> {
+    match git.run(["show-ref", "--verify", "--quiet", reference]) {
+        Ok(_) => Ok(true),
+        Err(GitError::CommandFailed { .. }) => Ok(false),
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
+
+fn output_backups<W: Write>(writer: &mut W, backups: &[BackupEntry]) -> Result<()> {
+    if backups.is_empty() {
+        writeln!(writer, "No backups found")?;
+        return Ok(());
+    }
+
+    let name_width = backups
+        .iter()
+        .map(|entry| entry.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("NAME".len());
+    let time_width = backups
+        .iter()
+        .map(|entry| entry.timestamp.len())
+        .max()
+        .unwrap_or(0)
+        .max("CREATED".len());
+
+    writeln!(
+        writer,
+        "{:<name_width$}  {:<time_width$}  REF",
+        "NAME", "CREATED"
+    )?;
+    for entry in backups {
+        writeln!(
+            writer,
+            "{:<name_width$}  {:<time_width$}  {}",
+            entry.name, entry.timestamp, entry.reference
+        )?;
+    }
+    Ok(())
+}
+
+fn archive_path(repo: &RepoContext, name: &str, timestamp: &str) -> PathBuf {
+    let mut path = repo.common_dir().join("wtw").join("backup");
+    for segment in name.split('/') {
+        path.push(segment);
+    }
+    path.join(format!("{}.tar", timestamp))
+}
+
+fn write_archive(archive: &Path, root: &Path, files: &[&str]) -> io::Result<()> {
+    if let Some(parent) = archive.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut builder = tar::Builder::new(File::create(archive)?);
+    builder.follow_symlinks(false);
+    for file in files {
+        builder.append_path_with_name(root.join(file), file)?;
+    }
+    builder.into_inner()?.sync_all()
+}
+
+/// Format a time as a sortable UTC stamp like `20240131T235959Z`.
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days (Howard Hinnant), valid for the whole u64 range we get.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+fn run_git<I, S>(git: &GitRunner, args: I, what: &str) -> std::result::Result<GitOutput, AppError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    map_git_result(git.run(args), what)
+}
+
+fn run_git_in<I, S>(
+    git: &GitRunner,
+    dir: &Path,
+    args: I,
+    what: &str,
+) -> std::result::Result<GitOutput, AppError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    map_git_result(git.run_in(dir, args), what)
+}
+
+fn map_git_result(
+    result: std::result::Result<GitOutput, GitError>,
+    what: &str,
+) -> std::result::Result<GitOutput, AppError> {
+    match result {
+        Ok(output) => Ok(output),
+        Err(GitError::CommandFailed { stderr, .. }) => {
+            let message = stderr.trim();
+            if message.is_empty() {
+                Err(AppError::git(format!(
+                    "git {} failed without error output",
+                    what
+                )))
+            } else {
+                Err(AppError::git(message.to_string()))
+            }
+        }
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn utc_timestamp_formats_civil_time() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "19700101T000000Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(1_709_251_199);
+        assert_eq!(utc_timestamp(leap_day), "20240229T235959Z");
+    }
+
+    #[test]
+    fn select_backup_prefers_newest_and_honors_timestamp() {
+        let backups: Vec<BackupEntry> = [
+            "refs/wtw/backup/feature/auth/20240101T000000Z",
+            "refs/wtw/backup/feature/auth/20240102T000000Z",
+            "refs/wtw/backup/feature/20240103T000000Z",
+        ]
+        .into_iter()
+        .filter_map(parse_backup_ref)
+        .collect();
+
+        let latest = select_backup(&backups, "feature/auth", None).unwrap();
+        assert_eq!(latest.timestamp, "20240102T000000Z");
+
+        let older = select_backup(&backups, "feature/auth", Some("20240101T000000Z")).unwrap();
+        assert_eq!(older.name, "feature/auth");
+
+        let by_ref =
+            select_backup(&backups, "refs/wtw/backup/feature/20240103T000000Z", None).unwrap();
+        assert_eq!(by_ref.name, "feature");
+
+        assert!(select_backup(&backups, "missing", None).is_err());
+    }
+
+    #[test]
+    fn timestamp_order_sorts_same_second_backups_by_suffix() {
+        let mut timestamps = vec![
+            "20240101T000000Z-10",
+            "20240101T000001Z",
+            "20240101T000000Z-2",
+            "20240101T000000Z",
+        ];
+        timestamps.sort_by_key(|timestamp| timestamp_order(timestamp));
+        assert_eq!(
+            timestamps,
+            [
+                "20240101T000000Z",
+                "20240101T000000Z-2",
+                "20240101T000000Z-10",
+                "20240101T000001Z",
+            ]
+        );
+    }
+
+    #[test]
+    fn ref_key_joins_components_with_slashes() {
+        assert_eq!(ref_key("feature\\auth"), "feature/auth");
+        assert_eq!(ref_key("feature/auth"), "feature/auth");
+        assert_eq!(ref_key("my dir"), "my-dir");
+        assert_eq!(ref_key("a..b/.hidden/x.lock/y:z"), "a.b/hidden/x-lock/y-z");
+        assert_eq!(ref_key("wip@{1}"), "wip@-1}");
+    }
+
+    #[test]
+    fn select_backup_accepts_display_names() {
+        let backups: Vec<BackupEntry> = ["refs/wtw/backup/feature/auth/20240101T000000Z"]
+            .into_iter()
+            .filter_map(parse_backup_ref)
+            .collect();
+        assert!(select_backup(&backups, "feature\\auth", None).is_ok());
+    }
+}
@@ -0,0 +1,379 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use unicode_width::UnicodeWidthStr;
+
+use crate::cli::CleanCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::backup::{self, Backup};
+use crate::worktree::{common, list, remove};
+
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &CleanCommand) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
+    let current_worktree = common::normalize_path(repo.worktree_root());
+    let base_branch = common::resolve_base_branch(git, config, &worktrees, cmd.base.as_deref())?;
+
+    let candidates = collect_candidates(
+        git,
+        &worktrees,
+        &base_dir,
+        &current_worktree,
+        &base_branch,
+        cmd.force,
+    )?;
+
+    let mut stdout = io::stdout().lock();
+    if candidates.is_empty() {
+        writeln!(
+            stdout,
+            "No worktrees with branches merged into '{}'",
+            base_branch
+        )?;
+        return Ok(());
+    }
+
+    output_table(&mut stdout, &candidates)?;
+
+    let removable: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|candidate| candidate.skip.is_none())
+        .collect();
+    if removable.is_empty() {
+        writeln!(stdout, "\nNothing to remove")?;
+        return Ok(());
+    }
+    if cmd.dry_run {
+        return Ok(());
+    }
+
+    if !cmd.yes {
+        let question = format!(
+            "Remove {} worktree{} and {} branch{}?",
+            removable.len(),
+            common::plural(removable.len(), "", "s"),
+            if removable.len() == 1 { "its" } else { "their" },
+            common::plural(removable.len(), "", "es"),
+        );
+        if !common::stdin_is_terminal() {
+            return Err(AppError::user(format!(
+                "refusing to remove {} worktree{} without confirmation\n\
+                 Re-run with --yes to remove {}.",
+                removable.len(),
+                common::plural(removable.len(), "", "s"),
+                common::plural(removable.len(), "it", "them")
+            ))
+            .into());
+        }
+        stdout.flush()?;
+        if !common::confirm(&question)? {
+            writeln!(stdout, "Aborted; no worktrees were removed")?;
+            return Ok(());
+        }
+    }
+
+    writeln!(stdout)?;
+    let mut failures = 0usize;
+    for candidate in removable {
+        match remove_candidate(repo, git, candidate) {
+            Ok(backup) => {
+                writeln!(
+                    stdout,
+                    "Removed worktree '{}' at {}",
+                    candidate.name,
+                    candidate.path.display()
+                )?;
+                writeln!(stdout, "Removed branch '{}'", candidate.branch)?;
+                if let Some(backup) = backup {
+                    backup::report(&mut stdout, &backup)?;
+                }
+            }
+            Err(err) => {
+                failures += 1;
+                eprintln!("Failed to remove worktree '{}': {}", candidate.name, err);
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(AppError::git(format!(
+            "failed to remove {} worktree{}",
+            failures,
+            common::plural(failures, "", "s")
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CleanReason {
+    Merged,
+    UpstreamGone,
+}
+
+impl CleanReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            CleanReason::Merged => "merged",
+            CleanReason::UpstreamGone => "upstream gone",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SkipReason {
+    Current,
+    Dirty,
+    Locked,
+    Unmerged,
+}
+
+impl SkipReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            SkipReason::Current => "skip (current)",
+            SkipReason::Dirty => "skip (dirty)",
+            SkipReason::Locked => "skip (locked)",
+            SkipReason::Unmerged => "skip (unmerged)",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Candidate {
+    name: String,
+    branch: String,
+    path: PathBuf,
+    reason: CleanReason,
+    dirty: bool,
+    locked: bool,
+    skip: Option<SkipReason>,
+}
+
+fn collect_candidates(
+    git: &GitRunner,
+    worktrees: &[WorktreeInfo],
+    base_dir: &Path,
+    current_worktree: &Path,
+    base_branch: &str,
+    force: bool,
+) -> Result<Vec<Candidate>> {
+    let mut candidates = Vec::new();
+    let base_tip = commit_id(git, base_branch)?;
+
+    for info in worktrees {
+        if info.is_main || info.prunable.is_some() || !common::is_managed(info, base_dir) {
+            continue;
+        }
+        let Some(branch) = info.branch.as_deref() else {
+            continue;
+        };
+        if branch == base_branch {
+            continue;
+        }
+
+        let merged = is_merged(git, branch, base_branch)?;
+        // A branch that never moved since it was created has no commits of
+        // its own, even once the base branch has advanced past it, so there
+        // is nothing "merged". Without a reflog, only a branch still at the
+        // base tip is known to be fresh.
+        let own_commits = match creation_commit(git, branch)? {
+            Some(created) => created != info.head,
+            None => info.head != base_tip,
+        };
+        let reason = if merged && own_commits {
+            CleanReason::Merged
+        } else if upstream_gone(git, branch)? {
+            CleanReason::UpstreamGone
+        } else {
+            continue;
+        };
+        // An upstream-gone branch may still hold commits that never reached
+        // the base branch; deleting those needs --force.
+        let unmerged = !merged;
+
+        let path = common::normalize_path(&info.path);
+        let dirty = list::determine_status(git, &path)? != "clean";
+        let locked = info.locked.is_some();
+        let skip = if path == current_worktree {
+            Some(SkipReason::Current)
+        } else if locked && !force {
+            Some(SkipReason::Locked)
+        } else if dirty && !force {
+            Some(SkipReason::Dirty)
+        } else if unmerged && !force {
+            Some(SkipReason::Unmerged)
+        } else {
+            None
+        };
+
+        candidates.push(Candidate {
+            name: common::display_name(info, base_dir),
+            branch: branch.to_string(),
+            path,
+            reason,
+            dirty,
+            locked,
+            skip,
+        });
+    }
+
+    Ok(candidates)
+}
+
+fn is_merged(git: &GitRunner, branch: &str, base_branch: &str) -> Result<bool> {
+    let output = git
+        .run_with_status([
+            "merge-base".to_string(),
+            "--is-ancestor".to_string(),
+            format!("refs/heads/{}", branch),
+            base_branch.to_string(),
+        ])
+        .map_err(|err| AppError::git(err.to_string()))?;
+
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => {
+            let message = output.stderr().trim();
+            if message.is_empty() {
+                Err(AppError::git(format!(
+                    "failed to check whether '{}' is merged into '{}'",
+                    branch, base_branch
+                ))
+                .into())
+            } else {
+                Err(AppError::git(message.to_string()).into())
+            }
+        }
+    }
+}
+
+/// The commit `branch` was created at: the oldest entry of its reflog, or
+/// `None` when the branch has no reflog.
+fn creation_commit(git: &GitRunner, branch: &str) -> Result<Option<String>> {
+    let output = git
+        .run([
+            "reflog".to_string(),
+            "show".to_string(),
+            "--format=%H".to_string(),
+            format!("refs/heads/{}", branch),
+            "--".to_string(),
+        ])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    Ok(output.stdout().lines().last().map(str::to_string))
+}
+
+fn commit_id(git: &GitRunner, rev: &str) -> Result<String> {
+    let output = git
+        .run([
+            "rev-parse".to_string(),
+            "--verify".to_string(),
+            format!("{}^{{commit}}", rev),
+        ])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    Ok(output.stdout().trim().to_string())
+}
+
+fn upstream_gone(git: &GitRunner, branch: &str) -> Result<bool> {
+    let output = git
+        .run([
+            "for-each-ref".to_string(),
+            "--format=%(upstream:track)".to_string(),
+            format!("refs/heads/{}", branch),
+        ])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    Ok(output.stdout().trim() == "[gone]")
+}
+
+fn remove_candidate(
+    repo: &RepoContext,
+    git: &GitRunner,
+    candidate: &Candidate,
+) -> std::result::Result<Option<Backup>, AppError> {
+    let backup = if candidate.dirty {
+        Some(backup::create(
+            repo,
+            git,
+            &candidate.name,
+            &candidate.path,
+            Some(&candidate.branch),
+        )?)
+    } else {
+        None
+    };
+
+    // git only removes a locked worktree when --force is given twice.
+    let force = if candidate.locked {
+        2
+    } else {
+        u8::from(candidate.dirty)
+    };
+    remove::remove_worktree(git, &candidate.path, force)?;
+    // The merge check above was done against the base branch rather than
+    // HEAD, so `git branch -d` could still refuse; delete unconditionally.
+    // Unmerged upstream-gone branches only get here with --force.
+    remove::remove_branch(git, &candidate.branch, true)?;
+    Ok(backup)
+}
+
+fn render_status(candidate: &Candidate) -> String {
+    let mut status = if candidate.dirty { "dirty" } else { "clean" }.to_string();
+    if candidate.locked {
+        status.push_str(", locked");
+    }
+    status
+}
+
+fn output_table<W: Write>(writer: &mut W, candidates: &[Candidate]) -> Result<()> {
+    let statuses: Vec<String> = candidates.iter().map(render_status).collect();
+    let actions: Vec<&str> = candidates
+        .iter()
+        .map(|candidate| candidate.skip.map_or("remove", SkipReason::as_str))
+        .collect();
+
+    let mut name_width = "PATH".len();
+    let mut branch_width = "BRANCH".len();
+    let mut reason_width = "REASON".len();
+    let mut status_width = "STATUS".len();
+    for (idx, candidate) in candidates.iter().enumerate() {
+        name_width = name_width.max(candidate.name.width());
+        branch_width = branch_width.max(candidate.branch.width());
+        reason_width = reason_width.max(candidate.reason.as_str().len());
+        status_width = status_width.max(statuses[idx].len());
+    }
+
+    writeln!(
+        writer,
+        "{} {} {:<reason_width$} {:<status_width$} ACTION",
+        common::pad("PATH", name_width),
+        common::pad("BRANCH", branch_width),
+        "REASON",
+        "STATUS"
+    )?;
+    writeln!(
+        writer,
+        "{:-<name_width$} {:-<branch_width$} {:-<reason_width$} {:-<status_width$} ------",
+        "", "", "", ""
+    )?;
+    for (idx, candidate) in candidates.iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {} {:<reason_width$} {:<status_width$} {}",
+            common::pad(&candidate.name, name_width),
+            common::pad(&candidate.branch, branch_width),
+            candidate.reason.as_str(),
+            statuses[idx],
+            actions[idx]
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,16 @@
-use std::path::{Path, PathBuf};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
+use anyhow::Result;
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::runner::GitRunner;
 use crate::git::worktree::WorktreeInfo;
 
 pub fn normalize_path(path: &Path) -> PathBuf {
@@ -24,10 +35,10 @@ pub fn display_name(info: &WorktreeInfo, base_dir: &Path) -> String {
     let info_path = normalize_path(&info.path);
     let base_dir = normalize_path(base_dir);
 
-    if let Ok(relative) = info_path.strip_prefix(&base_dir) {
-        if !relative.as_os_str().is_empty() {
-            return components_to_string(relative);
-        }
+    if let Ok(relative) = info_path.strip_prefix(&base_dir)
+        && !relative.as_os_str().is_empty()
+    {
+        return components_to_string(relative);
     }
 
     info_path
@@ -36,6 +47,287 @@ pub fn display_name(info: &WorktreeInfo, base_dir: &Path) -> String {
         .unwrap_or_else(|| info_path.to_string_lossy().to_string())
 }
 
+/// Display names for `worktrees`, with colliding names made unique.
+///
+/// Worktrees outside the base directory are named after their directory, so
+/// two of them (or one and a managed worktree) can share a name. Those
+/// unmanaged names gain parent directories (`other/tmp`) until they are
+/// unique, falling back to the full path; managed names never change.
+pub fn display_names(worktrees: &[WorktreeInfo], base_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = worktrees
+        .iter()
+        .map(|info| display_name(info, base_dir))
+        .collect();
+    let mut depths = vec![1; worktrees.len()];
+
+    loop {
+        // Every colliding name grows in the same pass, so `a/tmp` and
+        // `b/tmp` both move away from `tmp`.
+        let before = names.clone();
+        let mut changed = false;
+        for (index, info) in worktrees.iter().enumerate() {
+            let collides = before
+                .iter()
+                .enumerate()
+                .any(|(other, name)| other != index && *name == before[index]);
+            if !collides || is_managed(info, base_dir) {
+                continue;
+            }
+            let path = normalize_path(&info.path);
+            let parts: Vec<&OsStr> = path
+                .components()
+                .filter_map(|component| match component {
+                    Component::Normal(part) => Some(part),
+                    _ => None,
+                })
+                .collect();
+            if depths[index] >= parts.len() {
+                let full = path.to_string_lossy().into_owned();
+                changed |= names[index] != full;
+                names[index] = full;
+                continue;
+            }
+            depths[index] += 1;
+            names[index] = components_to_string(
+                &parts[parts.len() - depths[index]..]
+                    .iter()
+                    .collect::<PathBuf>(),
+            );
+            changed = true;
+        }
+        if !changed {
+            return names;
+        }
+    }
+}
+
+/// Human-readable lock state; git allows locking without a reason.
+pub fn describe_lock(reason: &str) -> String {
+    if reason.is_empty() {
+        "locked".to_string()
+    } else {
+        format!("locked: {}", reason)
+    }
+}
+
+/// Determine the branch that other branches are compared against.
+///
+/// Precedence: an explicit value (e.g. `--base`), `defaults.base_branch` from
+/// the config, then the branch checked out in the main worktree. The result is
+/// verified to resolve to a commit.
+pub fn resolve_base_branch(
+    git: &GitRunner,
+    config: &Config,
+    worktrees: &[WorktreeInfo],
+    explicit: Option<&str>,
+) -> Result<String> {
+    let base = explicit
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            config
+                .defaults
+                .base_branch
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        })
+        .or_else(|| {
+            worktrees
+                .iter()
+                .find(|info| info.is_main)
+                .and_then(|info| info.branch.clone())
+        })
+        .ok_or_else(|| AppError::user("unable to determine the base branch (use --base)"))?;
+
+    let output = git
+        .run_with_status([
+            "rev-parse".to_string(),
+            "--verify".to_string(),
+            "--quiet".to_string(),
+            format!("{}^{{commit}}", base),
+        ])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    if !output.status.success() {
+        return Err(AppError::user(format!("base branch '{}' not found", base)).into());
+    }
+
+    Ok(base)
+}
+
+/// Whether a target contains wildcard characters understood by [`glob_match`].
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Minimal wildcard matching: `*` matches any run of characters (including
+/// path separators) and `?` matches exactly one character.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&ch| ch == '*')
+}
+
+/// Whether `haystack` contains the characters of `needle` in order, e.g.
+/// `fth` in `feature/auth`.
+pub fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|wanted| chars.any(|c| c == wanted))
+}
+
+pub fn stdin_is_terminal() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Ask a yes/no question on stderr and read the answer from stdin.
+///
+/// Anything other than `y`/`yes` (case-insensitive) counts as "no", including
+/// an empty line or EOF.
+pub fn confirm(question: &str) -> Result<bool> {
+    let mut stderr = io::stderr().lock();
+    write!(stderr, "{} [y/N] ", question)?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim().to_ascii_lowercase();
+    Ok(answer == "y" || answer == "yes")
+}
+
+/// Express `path` relative to `base`, walking up with `..` where needed.
+///
+/// Both paths are expected to be absolute and normalized; if they share no
+/// root (different drives on Windows), `path` is returned unchanged.
+pub fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let path_components: Vec<_> = path.components().collect();
+    let base_components: Vec<_> = base.components().collect();
+    let common = path_components
+        .iter()
+        .zip(&base_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return path.to_path_buf();
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in common..base_components.len() {
+        relative.push("..");
+    }
+    for component in &path_components[common..] {
+        relative.push(component);
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+/// Replace `path` with `contents` by renaming a complete temporary file into
+/// place, so that a concurrent reader never sees a partial file.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let temp = path.with_extension(format!("tmp{}", std::process::id()));
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path)
+}
+
+/// Remove the directories above `path` that are left empty, stopping at
+/// `base_dir` or at the first directory that still has entries.
+pub fn remove_empty_parents(path: &Path, base_dir: &Path) {
+    let mut current = path.parent();
+    while let Some(dir) = current {
+        if dir == base_dir || !dir.starts_with(base_dir) {
+            break;
+        }
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        current = dir.parent();
+    }
+}
+
+/// Left-align `text` in `width` terminal cells, so wide characters such as
+/// CJK line up in tables.
+pub fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+}
+
+/// Pick the singular or plural suffix for `count`.
+pub fn plural<'a>(count: usize, singular: &'a str, plural: &'a str) -> &'a str {
+    if count == 1 { singular } else { plural }
+}
+
+/// Upper bound on worker threads for [`parallel_map`]; each job usually
+/// spawns `git`, so more threads mostly add process contention.
+const MAX_PARALLEL_JOBS: usize = 8;
+
+/// Apply `f` to every item on a bounded pool of scoped threads, returning
+/// the results in input order.
+pub fn parallel_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_PARALLEL_JOBS)
+        .min(items.len());
+    if jobs <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut indexed: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+                        done.push((index, f(item)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    indexed.sort_by_key(|(index, _)| *index);
+    indexed.into_iter().map(|(_, result)| result).collect()
+}
+
 fn strip_extended_prefix(path: PathBuf) -> PathBuf {
     #[cfg(windows)]
     {
@@ -67,7 +359,7 @@ mod tests {
 
     fn make_info(path: &str, branch: Option<&str>, is_main: bool) -> WorktreeInfo {
         WorktreeInfo {
-            path: PathBuf::from(path),
+            path: native_path(path),
             head: "deadbeef".to_string(),
             branch: branch.map(|s| s.to_string()),
             is_main,
@@ -77,10 +369,20 @@ mod tests {
         }
     }
 
+    // Fixtures are written with Windows paths; map them onto an absolute
+    // POSIX path elsewhere so the assertions hold on every platform.
+    fn native_path(path: &str) -> PathBuf {
+        if cfg!(windows) {
+            PathBuf::from(path)
+        } else {
+            PathBuf::from(format!("/{}", path.replace(":\\", "/").replace('\\', "/")))
+        }
+    }
+
     #[test]
     fn display_main_worktree_as_at() {
         let info = make_info("C:\\repo", Some("main"), true);
-        let base_dir = PathBuf::from("C:\\repo\\worktree");
+        let base_dir = native_path("C:\\repo\\worktree");
         assert_eq!(display_name(&info, &base_dir), "@");
     }
 
@@ -91,13 +393,41 @@ mod tests {
             Some("feature/auth"),
             false,
         );
-        let base_dir = PathBuf::from("C:\\repo\\worktree");
+        let base_dir = native_path("C:\\repo\\worktree");
         assert_eq!(
             display_name(&info, &base_dir),
             format!("feature{}auth", std::path::MAIN_SEPARATOR)
         );
     }
 
+    #[test]
+    fn display_names_disambiguate_unmanaged_collisions() {
+        let base_dir = native_path("C:\\repo\\worktree");
+        let worktrees = [
+            make_info("C:\\repo", Some("main"), true),
+            make_info("C:\\repo\\worktree\\tmp", Some("tmp"), false),
+            make_info("C:\\scratch\\a\\tmp", None, false),
+            make_info("C:\\scratch\\b\\tmp", None, false),
+            make_info("C:\\other\\spike", None, false),
+        ];
+        let sep = std::path::MAIN_SEPARATOR;
+        assert_eq!(
+            display_names(&worktrees, &base_dir),
+            [
+                "@".to_string(),
+                "tmp".to_string(),
+                format!("a{sep}tmp"),
+                format!("b{sep}tmp"),
+                "spike".to_string(),
+            ]
+        );
+        // Without the managed `tmp`, both unmanaged names still grow.
+        assert_eq!(
+            display_names(&worktrees[2..4], &base_dir),
+            [format!("a{sep}tmp"), format!("b{sep}tmp")]
+        );
+    }
+
     #[test]
     fn managed_worktree_within_base_dir() {
         let info = make_info(
@@ -105,14 +435,73 @@ mod tests {
             Some("feature/auth"),
             false,
         );
-        let base_dir = PathBuf::from("C:\\repo\\worktree");
+        let base_dir = native_path("C:\\repo\\worktree");
         assert!(is_managed(&info, &base_dir));
     }
 
+    #[test]
+    fn glob_match_supports_star_and_question_mark() {
+        assert!(glob_match("experiment/*", "experiment/a"));
+        assert!(glob_match("experiment/*", "experiment/a/b"));
+        assert!(glob_match("feat-?", "feat-a"));
+        assert!(glob_match("*auth*", "feature/auth-refactor"));
+        assert!(!glob_match("experiment/*", "feature/experiment"));
+        assert!(!glob_match("feat-?", "feat-ab"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn relative_path_walks_up_to_the_common_ancestor() {
+        let base = native_path("C:\\repo\\worktree\\feature\\auth\\src");
+        assert_eq!(
+            relative_path(
+                &native_path("C:\\repo\\worktree\\feature\\auth\\src\\main.rs"),
+                &base
+            ),
+            PathBuf::from("main.rs")
+        );
+        assert_eq!(
+            relative_path(&native_path("C:\\repo\\worktree\\fix\\src"), &base),
+            [".."; 3]
+                .iter()
+                .collect::<PathBuf>()
+                .join("fix")
+                .join("src")
+        );
+        assert_eq!(relative_path(&base, &base), PathBuf::from("."));
+    }
+
+    #[test]
+    fn parallel_map_keeps_input_order() {
+        let items: Vec<usize> = (0..50).collect();
+        let doubled = parallel_map(&items, |n| {
+            // Finish out of order so the sort is what keeps the order.
+            thread::sleep(std::time::Duration::from_micros(((50 - n) * 20) as u64));
+            n * 2
+        });
+        assert_eq!(doubled, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+        assert!(parallel_map(&[] as &[usize], |n| *n).is_empty());
+    }
+
+    #[test]
+    fn remove_empty_parents_stops_at_base_dir_and_non_empty_dirs() {
+        let temp = tempfile::TempDir::new().expect("temp dir");
+        let base_dir = temp.path().join("worktree");
+        let old_path = base_dir.join("feature").join("deep").join("auth");
+        fs::create_dir_all(base_dir.join("feature").join("other")).expect("sibling");
+        fs::create_dir_all(base_dir.join("feature").join("deep")).expect("parent");
+
+        remove_empty_parents(&old_path, &base_dir);
+
+        assert!(!base_dir.join("feature").join("deep").exists());
+        assert!(base_dir.join("feature").join("other").exists());
+        assert!(base_dir.exists());
+    }
+
     #[test]
     fn unmanaged_worktree_outside_base_dir() {
         let info = make_info("D:\\temp\\feature", Some("feature"), false);
-        let base_dir = PathBuf::from("C:\\repo\\worktree");
+        let base_dir = native_path("C:\\repo\\worktree");
         assert!(!is_managed(&info, &base_dir));
     }
 }
@@ -0,0 +1,222 @@
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::git::worktree::WorktreeInfo;
+use crate::worktree::common;
+
+/// One `wtw list --filter` expression, optionally negated with a leading `!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Filter {
+    negate: bool,
+    kind: FilterKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FilterKind {
+    Status(StatusValue),
+    Branch(Pattern),
+    Name(Pattern),
+    Managed,
+    Locked,
+    Prunable,
+    Detached,
+    Main,
+    Current,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatusValue {
+    Clean,
+    Dirty,
+}
+
+/// `key=value` compares exactly, `key~pattern` uses [`common::glob_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern {
+    text: String,
+    glob: bool,
+}
+
+impl Pattern {
+    fn matches(&self, value: &str) -> bool {
+        if self.glob {
+            common::glob_match(&self.text, value)
+        } else {
+            self.text == value
+        }
+    }
+}
+
+/// What a worktree looks like to a filter before any Git call is made.
+pub(crate) struct FilterTarget<'a> {
+    pub(crate) info: &'a WorktreeInfo,
+    pub(crate) name: &'a str,
+    pub(crate) base_dir: &'a Path,
+    pub(crate) is_current: bool,
+}
+
+impl Filter {
+    pub(crate) fn parse(expr: &str) -> Result<Self, AppError> {
+        let trimmed = expr.trim();
+        let (negate, body) = match trimmed.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, trimmed),
+        };
+
+        let kind = match body.find(['=', '~']) {
+            Some(index) => {
+                let key = body[..index].trim();
+                let glob = body[index..].starts_with('~');
+                let value = body[index + 1..].trim();
+                if value.is_empty() {
+                    return Err(invalid(expr, "missing value"));
+                }
+                let pattern = Pattern {
+                    text: value.to_string(),
+                    glob,
+                };
+                match key {
+                    "status" if !glob => FilterKind::Status(match value {
+                        "clean" => StatusValue::Clean,
+                        "dirty" => StatusValue::Dirty,
+                        _ => return Err(invalid(expr, "status must be 'clean' or 'dirty'")),
+                    }),
+                    "branch" => FilterKind::Branch(pattern),
+                    "name" => FilterKind::Name(pattern),
+                    _ => return Err(invalid(expr, "unknown key")),
+                }
+            }
+            None => match body {
+                "managed" => FilterKind::Managed,
+                "locked" => FilterKind::Locked,
+                "prunable" => FilterKind::Prunable,
+                "detached" => FilterKind::Detached,
+                "main" => FilterKind::Main,
+                "current" => FilterKind::Current,
+                "dirty" => FilterKind::Status(StatusValue::Dirty),
+                "clean" => FilterKind::Status(StatusValue::Clean),
+                _ => return Err(invalid(expr, "unknown filter")),
+            },
+        };
+
+        Ok(Self { negate, kind })
+    }
+
+    /// Whether the filter needs `git status`, i.e. can only be applied with
+    /// [`Filter::matches_status`].
+    pub(crate) fn needs_status(&self) -> bool {
+        matches!(self.kind, FilterKind::Status(_))
+    }
+
+    /// Apply a filter that does not need the status; status filters pass.
+    pub(crate) fn matches_worktree(&self, target: &FilterTarget<'_>) -> bool {
+        let info = target.info;
+        let matched = match &self.kind {
+            FilterKind::Status(_) => return true,
+            FilterKind::Branch(pattern) => info
+                .branch
+                .as_deref()
+                .is_some_and(|branch| pattern.matches(branch)),
+            FilterKind::Name(pattern) => pattern.matches(target.name),
+            FilterKind::Managed => common::is_managed(info, target.base_dir),
+            FilterKind::Locked => info.locked.is_some(),
+            FilterKind::Prunable => info.prunable.is_some(),
+            FilterKind::Detached => info.is_detached,
+            FilterKind::Main => info.is_main,
+            FilterKind::Current => target.is_current,
+        };
+        matched != self.negate
+    }
+
+    /// Apply a status filter to a `"clean"`/`"dirty"` label; other filters
+    /// pass.
+    pub(crate) fn matches_status(&self, status: &str) -> bool {
+        let FilterKind::Status(value) = self.kind else {
+            return true;
+        };
+        let dirty = status != "clean";
+        (dirty == (value == StatusValue::Dirty)) != self.negate
+    }
+}
+
+fn invalid(expr: &str, reason: &str) -> AppError {
+    AppError::user(format!(
+        "invalid --filter '{}': {}\n\
+         Use status=clean|dirty, branch=<name>, branch~<glob>, name=<name>, name~<glob>,\n\
+         managed, locked, prunable, detached, main or current (prefix with '!' to negate).",
+        expr, reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn info(path: &str, branch: Option<&str>) -> WorktreeInfo {
+        WorktreeInfo {
+            path: PathBuf::from(path),
+            head: "0123456789abcdef".to_string(),
+            branch: branch.map(str::to_string),
+            is_main: false,
+            is_detached: branch.is_none(),
+            locked: None,
+            prunable: None,
+        }
+    }
+
+    fn matches(expr: &str, info: &WorktreeInfo, name: &str) -> bool {
+        Filter::parse(expr)
+            .unwrap()
+            .matches_worktree(&FilterTarget {
+                info,
+                name,
+                base_dir: Path::new("/src/worktree"),
+                is_current: false,
+            })
+    }
+
+    #[test]
+    fn matches_branch_name_and_flags() {
+        let feature = info("/src/worktree/feature/auth", Some("feature/auth"));
+        assert!(matches("branch~feature/*", &feature, "feature/auth"));
+        assert!(matches("branch=feature/auth", &feature, "feature/auth"));
+        assert!(!matches("branch=feature", &feature, "feature/auth"));
+        assert!(matches("name~*auth", &feature, "feature/auth"));
+        assert!(matches("managed", &feature, "feature/auth"));
+        assert!(!matches("!managed", &feature, "feature/auth"));
+        assert!(!matches("locked", &feature, "feature/auth"));
+
+        let detached = info("/elsewhere/tmp", None);
+        assert!(!matches("branch~*", &detached, "/elsewhere/tmp"));
+        assert!(matches("detached", &detached, "/elsewhere/tmp"));
+        assert!(matches("! managed", &detached, "/elsewhere/tmp"));
+    }
+
+    #[test]
+    fn status_filters_wait_for_the_status() {
+        let dirty = Filter::parse("status=dirty").unwrap();
+        assert!(dirty.needs_status());
+        assert!(dirty.matches_status("dirty"));
+        assert!(!dirty.matches_status("clean"));
+        assert!(Filter::parse("!dirty").unwrap().matches_status("clean"));
+        assert!(!Filter::parse("locked").unwrap().needs_status());
+    }
+
+    #[test]
+    fn rejects_unknown_expressions() {
+        for expr in [
+            "status=stale",
+            "status~d*",
+            "colour=red",
+            "branch=",
+            "bogus",
+        ] {
+            let err = Filter::parse(expr).unwrap_err();
+            assert!(
+                err.to_string().contains("invalid --filter"),
+                "{expr}: {err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::RecentCommand;
+use crate::config::Config;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::common;
+use crate::worktree::resolve::Resolver;
+
+/// Entries kept in the history file; older ones are dropped on write.
+const MAX_ENTRIES: usize = 1000;
+
+/// One successful `wtw cd`, from one worktree root to another.
+#[derive(Debug, Clone, PartialEq)]
+struct Visit {
+    time: u64,
+    from: PathBuf,
+    to: PathBuf,
+}
+
+/// The per-repository `wtw cd` history, oldest visit first.
+#[derive(Debug, Default)]
+pub(crate) struct History {
+    visits: Vec<Visit>,
+}
+
+impl History {
+    /// Read the history; a missing or unreadable file is an empty history.
+    pub(crate) fn load(repo: &RepoContext) -> Self {
+        let contents = fs::read_to_string(history_path(repo)).unwrap_or_default();
+        Self::parse(&contents)
+    }
+
+    fn parse(contents: &str) -> Self {
+        let visits = contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let time = fields.next()?.parse().ok()?;
+                let from = PathBuf::from(fields.next()?);
+                let to = PathBuf::from(fields.next()?);
+                Some(Visit { time, from, to })
+            })
+            .collect();
+        Self { visits }
+    }
+
+    fn serialize(&self) -> String {
+        self.visits
+            .iter()
+            .map(|visit| {
+                format!(
+                    "{}\t{}\t{}\n",
+                    visit.time,
+                    visit.from.display(),
+                    visit.to.display()
+                )
+            })
+            .collect()
+    }
+
+    /// The worktree visited most recently before `current`, like `cd -`.
+    ///
+    /// Both ends of every visit count, so repeated `wtw cd -` toggles between
+    /// two worktrees. Only paths accepted by `is_worktree` are returned.
+    pub(crate) fn previous(
+        &self,
+        current: &Path,
+        is_worktree: impl Fn(&Path) -> bool,
+    ) -> Option<PathBuf> {
+        self.visits
+            .iter()
+            .rev()
+            .flat_map(|visit| [&visit.to, &visit.from])
+            .find(|path| path.as_path() != current && is_worktree(path))
+            .cloned()
+    }
+
+    /// Rank every visited worktree by frequency and recency at `now`.
+    pub(crate) fn frecency(&self, now: u64) -> Frecency {
+        let mut ranks: HashMap<PathBuf, Rank> = HashMap::new();
+        for visit in &self.visits {
+            let rank = ranks.entry(visit.to.clone()).or_default();
+            rank.visits += 1;
+            rank.last_visit = rank.last_visit.max(visit.time);
+            rank.score += visit_weight(now.saturating_sub(visit.time));
+        }
+        Frecency { ranks }
+    }
+}
+
+/// How often and how recently each worktree was visited with `wtw cd`.
+#[derive(Debug, Default)]
+pub(crate) struct Frecency {
+    ranks: HashMap<PathBuf, Rank>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Rank {
+    pub(crate) visits: usize,
+    pub(crate) last_visit: u64,
+    pub(crate) score: f64,
+}
+
+impl Frecency {
+    pub(crate) fn rank(&self, path: &Path) -> Option<Rank> {
+        self.ranks.get(&common::normalize_path(path)).copied()
+    }
+
+    pub(crate) fn score(&self, path: &Path) -> f64 {
+        self.rank(path).map_or(0.0, |rank| rank.score)
+    }
+
+    /// Stable sort, most frecent first; unvisited worktrees keep their order.
+    pub(crate) fn sort(&self, worktrees: &mut [&WorktreeInfo]) {
+        worktrees.sort_by(|a, b| self.score(&b.path).total_cmp(&self.score(&a.path)));
+    }
+}
+
+/// Recent visits count for more, the way `z`/`zoxide` age their entries.
+fn visit_weight(age_secs: u64) -> f64 {
+    const HOUR: u64 = 3_600;
+    const DAY: u64 = 24 * HOUR;
+    const WEEK: u64 = 7 * DAY;
+    match age_secs {
+        age if age < HOUR => 4.0,
+        age if age < DAY => 2.0,
+        age if age < WEEK => 0.5,
+        _ => 0.25,
+    }
+}
+
+/// Append a visit to the history file, dropping the oldest entries beyond
+/// the cap.
+pub(crate) fn record(repo: &RepoContext, from: &Path, to: &Path) -> io::Result<()> {
+    let path = history_path(repo);
+    let mut history = History::parse(&fs::read_to_string(&path).unwrap_or_default());
+    history.visits.push(Visit {
+        time: now(),
+        from: common::normalize_path(from),
+        to: common::normalize_path(to),
+    });
+    let excess = history.visits.len().saturating_sub(MAX_ENTRIES);
+    history.visits.drain(..excess);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // `cd` and completions can read the history while another `cd` writes.
+    common::write_atomic(&path, &history.serialize())
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn history_path(repo: &RepoContext) -> PathBuf {
+    repo.common_dir().join("wtw").join("history")
+}
+
+#[derive(Debug, Serialize)]
+struct RecentRow {
+    name: String,
+    path: String,
+    visits: usize,
+    last_visit: u64,
+    score: f64,
+}
+
+pub fn recent(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &RecentCommand,
+) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
+    let frecency = History::load(repo).frecency(now());
+
+    let mut visited: Vec<&WorktreeInfo> = Resolver::new(&worktrees, &base_dir, repo.repo_name())
+        .candidates()
+        .filter(|info| frecency.rank(&info.path).is_some())
+        .collect();
+    frecency.sort(&mut visited);
+
+    let rows: Vec<RecentRow> = visited
+        .into_iter()
+        .take(cmd.limit.unwrap_or(usize::MAX))
+        .filter_map(|info| {
+            let rank = frecency.rank(&info.path)?;
+            Some(RecentRow {
+                name: common::display_name(info, &base_dir),
+                path: common::normalize_path(&info.path)
+                    .to_string_lossy()
+                    .into_owned(),
+                visits: rank.visits,
+                last_visit: rank.last_visit,
+                score: rank.score,
+            })
+        })
+        .collect();
+
+    let mut stdout = io::stdout().lock();
+    if cmd.json {
+        serde_json::to_writer_pretty(&mut stdout, &rows)?;
+        writeln!(stdout)?;
+        return Ok(());
+    }
+    if rows.is_empty() {
+        writeln!(stdout, "No worktree history yet; use 'wtw cd' to build it")?;
+        return Ok(());
+    }
+
+    let now = now();
+    let ages: Vec<String> = rows
+        .iter()
+        .map(|row| describe_age(now.saturating_sub(row.last_visit)))
+        .collect();
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("NAME".len());
+    let age_width = ages
+        .iter()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max("LAST VISIT".len());
+
+    writeln!(
+        stdout,
+        "{:<name_width$} VISITS {:<age_width$} PATH",
+        "NAME", "LAST VISIT"
+    )?;
+    writeln!(
+        stdout,
+        "{:-<name_width$} ------ {:-<age_width$} ----",
+        "", ""
+    )?;
+    for (row, age) in rows.iter().zip(&ages) {
+        writeln!(
+            stdout,
+            "{:<name_width$} {:>6} {:<age_width$} {}",
+            row.name, row.visits, age, row.path
+        )?;
+    }
+    Ok(())
+}
+
+pub(crate) fn describe_age(secs: u64) -> String {
+    let (count, unit) = match secs {
+        s if s < 60 => return "just now".to_string(),
+        s if s < 3_600 => (s / 60, "minute"),
+        s if s < 86_400 => (s / 3_600, "hour"),
+        s => (s / 86_400, "day"),
+    };
+    format!(
+        "{} {}{} ago",
+        count,
+        unit,
+        common::plural(count as usize, "", "s")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visit(time: u64, from: &str, to: &str) -> Visit {
+        Visit {
+            time,
+            from: PathBuf::from(from),
+            to: PathBuf::from(to),
+        }
+    }
+
+    #[test]
+    fn history_round_trips_and_skips_malformed_lines() {
+        let history = History {
+            visits: vec![visit(10, "/repo", "/wt/a"), visit(20, "/wt/a", "/wt/b")],
+        };
+        let text = format!("garbage\n{}not-a-number\t/x\t/y\n", history.serialize());
+        assert_eq!(History::parse(&text).visits, history.visits);
+    }
+
+    #[test]
+    fn previous_toggles_between_the_last_two_worktrees() {
+        let history = History {
+            visits: vec![visit(10, "/repo", "/wt/a"), visit(20, "/wt/a", "/wt/b")],
+        };
+        let exists = |_: &Path| true;
+        assert_eq!(
+            history.previous(Path::new("/wt/b"), exists),
+            Some(PathBuf::from("/wt/a"))
+        );
+        assert_eq!(
+            history.previous(Path::new("/wt/a"), exists),
+            Some(PathBuf::from("/wt/b"))
+        );
+        assert_eq!(
+            history.previous(Path::new("/wt/b"), |path: &Path| path != Path::new("/wt/a")),
+            Some(PathBuf::from("/repo"))
+        );
+        assert_eq!(
+            History::default().previous(Path::new("/repo"), exists),
+            None
+        );
+    }
+
+    #[test]
+    fn frecency_favours_recent_visits_over_old_frequent_ones() {
+        let week = 7 * 86_400;
+        let now = 10 * week;
+        let history = History {
+            visits: vec![
+                visit(now - 3 * week, "/repo", "/wt/old"),
+                visit(now - 3 * week, "/repo", "/wt/old"),
+                visit(now - 3 * week, "/repo", "/wt/old"),
+                visit(now - 60, "/repo", "/wt/new"),
+            ],
+        };
+        let frecency = history.frecency(now);
+        let old = frecency.rank(Path::new("/wt/old")).unwrap();
+        assert_eq!(old.visits, 3);
+        assert!(frecency.score(Path::new("/wt/new")) > old.score);
+        assert_eq!(frecency.score(Path::new("/wt/never")), 0.0);
+    }
+
+    #[test]
+    fn describe_age_uses_the_largest_unit() {
+        assert_eq!(describe_age(5), "just now");
+        assert_eq!(describe_age(60), "1 minute ago");
+        assert_eq!(describe_age(7_200), "2 hours ago");
+        assert_eq!(describe_age(3 * 86_400), "3 days ago");
+    }
+}
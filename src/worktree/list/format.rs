@@ -0,0 +1,475 @@
+use std::io::Write;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::{DisplayRow, JsonEntry, render_divergence, render_reason};
+use crate::cli::JsonVersion;
+use crate::error::AppError;
+
+/// A column of the table, CSV and TSV output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Column {
+    Path,
+    Branch,
+    Head,
+    Status,
+    Changes,
+    Upstream,
+    UpDiff,
+    BaseDiff,
+    Locked,
+    Prunable,
+    Managed,
+    Committed,
+    Author,
+    AbsPath,
+    Subject,
+}
+
+const ALL_COLUMNS: [Column; 15] = [
+    Column::Path,
+    Column::Branch,
+    Column::Head,
+    Column::Status,
+    Column::Changes,
+    Column::Upstream,
+    Column::UpDiff,
+    Column::BaseDiff,
+    Column::Locked,
+    Column::Prunable,
+    Column::Managed,
+    Column::Committed,
+    Column::Author,
+    Column::AbsPath,
+    Column::Subject,
+];
+
+impl Column {
+    /// Lower-case name used by `--columns` and as the CSV/TSV header.
+    pub(super) fn id(self) -> &'static str {
+        match self {
+            Column::Path => "path",
+            Column::Branch => "branch",
+            Column::Head => "head",
+            Column::Status => "status",
+            Column::Changes => "changes",
+            Column::Upstream => "upstream",
+            Column::UpDiff => "up_diff",
+            Column::BaseDiff => "base_diff",
+            Column::Locked => "locked",
+            Column::Prunable => "prunable",
+            Column::Managed => "managed",
+            Column::Committed => "committed",
+            Column::Author => "author",
+            Column::AbsPath => "abs_path",
+            Column::Subject => "subject",
+        }
+    }
+
+    pub(super) fn header(self) -> String {
+        self.id().to_ascii_uppercase()
+    }
+
+    /// Whether the column needs the details collected for `--long`.
+    pub(super) fn needs_long(self) -> bool {
+        matches!(
+            self,
+            Column::UpDiff
+                | Column::BaseDiff
+                | Column::Committed
+                | Column::Author
+                | Column::Subject
+        )
+    }
+
+    /// The columns shown when `--columns` is not given. `managed` is left
+    /// out: the table marks unmanaged worktrees next to their name.
+    pub(super) fn defaults(long: bool) -> Vec<Column> {
+        ALL_COLUMNS
+            .into_iter()
+            .filter(|column| *column != Column::Managed && (long || !column.needs_long()))
+            .collect()
+    }
+
+    /// Parse a `--columns` entry, ignoring case and accepting `-` for `_`.
+    pub(super) fn parse(name: &str) -> Result<Self, AppError> {
+        let normalized = name.trim().to_ascii_lowercase().replace('-', "_");
+        ALL_COLUMNS
+            .into_iter()
+            .find(|column| column.id() == normalized)
+            .ok_or_else(|| {
+                let known: Vec<&str> = ALL_COLUMNS.iter().map(|column| column.id()).collect();
+                AppError::user(format!(
+                    "unknown column '{}'; available columns: {}",
+                    name,
+                    known.join(", ")
+                ))
+            })
+    }
+
+    /// The cell for `row`, `None` when there is nothing to show.
+    pub(super) fn value(self, row: &DisplayRow) -> Option<String> {
+        let long = row.long.as_ref();
+        let last_commit = long.and_then(|info| info.last_commit.as_ref());
+        match self {
+            Column::Path => Some(row.name.clone()),
+            Column::Branch => Some(row.branch_display.clone()),
+            Column::Head => Some(row.head.clone()),
+            Column::Status => row.status.clone(),
+            Column::Changes => row.worktree_status.as_ref().map(|status| status.symbols()),
+            Column::Upstream => row.upstream.clone(),
+            Column::UpDiff => long
+                .and_then(|info| info.upstream.as_ref())
+                .map(|diff| render_divergence(Some(diff))),
+            Column::BaseDiff => long
+                .and_then(|info| info.base.as_ref())
+                .map(|(_, diff)| render_divergence(Some(diff))),
+            Column::Locked => row
+                .locked
+                .as_deref()
+                .map(|reason| render_reason(Some(reason))),
+            Column::Prunable => row
+                .prunable
+                .as_deref()
+                .map(|reason| render_reason(Some(reason))),
+            Column::Managed => Some(if row.is_managed { "yes" } else { "no" }.to_string()),
+            Column::Committed => last_commit.map(|commit| commit.relative_date.clone()),
+            Column::Author => last_commit.map(|commit| commit.author.clone()),
+            Column::AbsPath => Some(row.abs_path.clone()),
+            Column::Subject => last_commit.map(|commit| commit.subject.clone()),
+        }
+    }
+}
+
+/// Write `rows` as comma- or tab-separated values with a header line.
+///
+/// CSV quotes fields as in RFC 4180; TSV has no quoting, so tabs and line
+/// breaks inside values become spaces. Missing values are empty.
+pub(super) fn write_delimited<W: Write>(
+    writer: &mut W,
+    rows: &[DisplayRow],
+    columns: &[Column],
+    separator: char,
+) -> Result<()> {
+    let escape = |field: &str| -> String {
+        if separator == '\t' {
+            field.replace(['\t', '\n', '\r'], " ")
+        } else if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    };
+
+    let header: Vec<&str> = columns.iter().map(|column| column.id()).collect();
+    writeln!(writer, "{}", header.join(&separator.to_string()))?;
+    for row in rows {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|column| escape(&column.value(row).unwrap_or_default()))
+            .collect();
+        writeln!(writer, "{}", fields.join(&separator.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Top-level fields of the version 1 JSON objects.
+const FIELDS_V1: [&str; 9] = [
+    "name",
+    "branch",
+    "head",
+    "status",
+    "upstream",
+    "path",
+    "abs_path",
+    "is_main",
+    "is_current",
+];
+
+/// Top-level fields of the version 2 JSON objects.
+const FIELDS_V2: [&str; 16] = [
+    "name",
+    "branch",
+    "head",
+    "head_short",
+    "status",
+    "changes",
+    "operation",
+    "upstream",
+    "path",
+    "abs_path",
+    "locked",
+    "prunable",
+    "is_main",
+    "is_current",
+    "is_managed",
+    "is_detached",
+];
+
+fn fields(version: JsonVersion) -> &'static [&'static str] {
+    match version {
+        JsonVersion::V1 => &FIELDS_V1,
+        JsonVersion::V2 => &FIELDS_V2,
+    }
+}
+
+/// Fields that only exist with `--long` in version 2; using one collects
+/// the details.
+const LONG_FIELDS: [&str; 6] = [
+    "ahead",
+    "behind",
+    "base_branch",
+    "base_ahead",
+    "base_behind",
+    "last_commit",
+];
+
+fn long_fields(version: JsonVersion) -> &'static [&'static str] {
+    match version {
+        JsonVersion::V1 => &[],
+        JsonVersion::V2 => &LONG_FIELDS,
+    }
+}
+
+/// A `--template` line such as `{{name}}\t{{branch}}`.
+///
+/// `{{field}}` is replaced by the field of the JSON object for the worktree,
+/// in the `--json-version` schema (`{{changes.staged}}` reaches into
+/// objects); `null` renders as nothing.
+/// `\t`, `\n` and `\\` are unescaped so templates work from any shell.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(Vec<String>),
+}
+
+impl Template {
+    pub(super) fn parse(text: &str, version: JsonVersion) -> Result<Self, AppError> {
+        let fields = fields(version);
+        let long_fields = long_fields(version);
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = text;
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("{{") {
+                let end = after.find("}}").ok_or_else(|| {
+                    AppError::user(format!("unterminated '{{{{' in template '{}'", text))
+                })?;
+                let path: Vec<String> =
+                    after[..end].trim().split('.').map(str::to_string).collect();
+                let field = path[0].as_str();
+                if !fields.contains(&field) && !long_fields.contains(&field) {
+                    return Err(AppError::user(format!(
+                        "unknown template field '{}'; available fields: {}",
+                        after[..end].trim(),
+                        fields
+                            .iter()
+                            .chain(long_fields.iter())
+                            .copied()
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(path));
+                rest = &after[end + 2..];
+                continue;
+            }
+
+            let mut chars = rest.chars();
+            let ch = chars.next().unwrap_or_default();
+            if ch == '\\' {
+                match chars.clone().next() {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    Some('\\') => literal.push('\\'),
+                    _ => {
+                        literal.push('\\');
+                        rest = chars.as_str();
+                        continue;
+                    }
+                }
+                chars.next();
+            } else {
+                literal.push(ch);
+            }
+            rest = chars.as_str();
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    pub(super) fn needs_long(&self) -> bool {
+        self.segments.iter().any(|segment| match segment {
+            Segment::Field(path) => LONG_FIELDS.contains(&path[0].as_str()),
+            Segment::Literal(_) => false,
+        })
+    }
+
+    pub(super) fn render(&self, entry: &JsonEntry) -> Result<String> {
+        let value = serde_json::to_value(entry)?;
+        let mut line = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => line.push_str(text),
+                Segment::Field(path) => {
+                    let field = path
+                        .iter()
+                        .try_fold(&value, |value, key| value.get(key.as_str()));
+                    match field {
+                        None | Some(Value::Null) => {}
+                        Some(Value::String(text)) => line.push_str(text),
+                        Some(other) => line.push_str(&other.to_string()),
+                    }
+                }
+            }
+        }
+        Ok(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{JsonLong, JsonWorktree, JsonWorktreeV2};
+    use super::*;
+
+    #[test]
+    fn template_unescapes_and_splits_fields() {
+        let template =
+            Template::parse(r"{{name}}\t{{ changes.staged }}\\x\q", JsonVersion::V2).unwrap();
+        assert_eq!(
+            template.segments,
+            vec![
+                Segment::Field(vec!["name".to_string()]),
+                Segment::Literal("\t".to_string()),
+                Segment::Field(vec!["changes".to_string(), "staged".to_string()]),
+                Segment::Literal("\\x\\q".to_string()),
+            ]
+        );
+        assert!(!template.needs_long());
+        assert!(
+            Template::parse("{{last_commit.subject}}", JsonVersion::V2)
+                .unwrap()
+                .needs_long()
+        );
+    }
+
+    #[test]
+    fn template_rejects_unknown_and_unterminated_fields() {
+        let err = Template::parse("{{colour}}", JsonVersion::V1).unwrap_err();
+        assert!(err.to_string().contains("unknown template field 'colour'"));
+        let err = Template::parse("{{name", JsonVersion::V1).unwrap_err();
+        assert!(err.to_string().contains("unterminated '{{'"));
+        let err = Template::parse("{{changes.staged}}", JsonVersion::V1).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unknown template field 'changes.staged'")
+        );
+        assert!(Template::parse("{{ahead}}", JsonVersion::V1).is_err());
+    }
+
+    #[test]
+    fn template_fields_match_the_json_output() {
+        let entry = JsonWorktree {
+            name: "feature/auth".to_string(),
+            branch: Some("feature/auth".to_string()),
+            head: "0123abcd".to_string(),
+            status: "clean".to_string(),
+            upstream: None,
+            path: "feature/auth".to_string(),
+            abs_path: "/src/worktree/feature/auth".to_string(),
+            is_main: false,
+            is_current: true,
+        };
+        assert_eq!(keys_of(&entry), expected_keys(JsonVersion::V1));
+
+        let template = Template::parse(
+            r"{{name}}\t{{upstream}}|{{status}}|{{is_current}}",
+            JsonVersion::V1,
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&JsonEntry::V1(entry)).unwrap(),
+            "feature/auth\t|clean|true"
+        );
+    }
+
+    #[test]
+    fn template_fields_match_the_v2_json_output() {
+        let entry = JsonWorktreeV2 {
+            name: "feature/auth".to_string(),
+            branch: Some("feature/auth".to_string()),
+            head: "0123abcd0123abcd0123abcd0123abcd0123abcd".to_string(),
+            head_short: "0123abcd".to_string(),
+            status: None,
+            changes: None,
+            operation: None,
+            upstream: None,
+            path: "worktree/feature/auth".to_string(),
+            abs_path: "/src/worktree/feature/auth".to_string(),
+            locked: None,
+            prunable: None,
+            is_main: false,
+            is_current: false,
+            is_managed: true,
+            is_detached: false,
+            long: Some(JsonLong {
+                ahead: Some(2),
+                behind: None,
+                base_branch: None,
+                base_ahead: None,
+                base_behind: None,
+                last_commit: None,
+            }),
+        };
+        assert_eq!(keys_of(&entry), expected_keys(JsonVersion::V2));
+
+        let template =
+            Template::parse("{{head_short}} {{path}}|{{ahead}}", JsonVersion::V2).unwrap();
+        assert_eq!(
+            template.render(&JsonEntry::V2(Box::new(entry))).unwrap(),
+            "0123abcd worktree/feature/auth|2"
+        );
+        assert!(Template::parse("{{is_managed}}", JsonVersion::V1).is_err());
+    }
+
+    fn keys_of<T: serde::Serialize>(entry: &T) -> Vec<String> {
+        let value = serde_json::to_value(entry).unwrap();
+        let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn expected_keys(version: JsonVersion) -> Vec<String> {
+        let long_fields = long_fields(version);
+        let mut expected: Vec<String> = fields(version)
+            .iter()
+            .chain(long_fields.iter())
+            .map(|field| field.to_string())
+            .collect();
+        expected.sort();
+        expected
+    }
+
+    #[test]
+    fn columns_parse_case_insensitively() {
+        assert_eq!(Column::parse("ABS-PATH").unwrap(), Column::AbsPath);
+        assert_eq!(Column::parse(" up_diff ").unwrap(), Column::UpDiff);
+        assert!(Column::parse("colour").is_err());
+        assert_eq!(Column::defaults(false).len(), 9);
+        assert_eq!(Column::defaults(true).len(), ALL_COLUMNS.len() - 1);
+        assert!(!Column::defaults(true).contains(&Column::Managed));
+    }
+}
@@ -0,0 +1,700 @@
+use std::cmp::{Ordering, Reverse};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::cli::{ColorChoice, JsonVersion, ListFormat, ListSort};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::{GitError, GitRunner};
+use crate::git::status::{self, ChangeCounts, Operation, WorktreeStatus};
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::common;
+use crate::worktree::filter::{Filter, FilterTarget};
+use crate::worktree::status_cache::StatusCache;
+use format::{Column, Template};
+use table::TableStyle;
+
+mod format;
+mod table;
+
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    pub format: ListFormat,
+    /// A line per worktree with `{{field}}` placeholders, instead of `format`.
+    pub template: Option<String>,
+    /// Table, CSV and TSV columns; empty for the defaults.
+    pub columns: Vec<String>,
+    /// Schema of the JSON objects (`json`, `ndjson` and templates); `None`
+    /// for the default.
+    pub json_version: Option<JsonVersion>,
+    /// Include ahead/behind counts and last-commit details.
+    pub long: bool,
+    /// Branch to compare against with `long` (overrides `defaults.base_branch`).
+    pub base: Option<String>,
+    /// Skip `git status` in every worktree.
+    pub no_status: bool,
+    /// Reuse statuses from the status cache where still valid.
+    pub cached: bool,
+    /// `--filter` expressions; a worktree is listed when all of them match.
+    pub filters: Vec<String>,
+    pub sort: Option<ListSort>,
+    pub reverse: bool,
+    /// Print only the display names, one per line.
+    pub names_only: bool,
+    /// Whether the table is colorized.
+    pub color: ColorChoice,
+}
+
+pub fn run(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    options: ListOptions,
+) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let locations = Locations {
+        base_dir: common::normalize_path(&config.resolved_base_dir(repo.main_root())),
+        current_worktree: common::normalize_path(repo.worktree_root()),
+        main_root: common::normalize_path(repo.main_root()),
+    };
+
+    let filters = options
+        .filters
+        .iter()
+        .map(|expr| Filter::parse(expr))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let filter_on_status = filters.iter().any(Filter::needs_status);
+    if filter_on_status && options.no_status {
+        return Err(AppError::user("a status filter cannot be combined with --no-status").into());
+    }
+
+    let json_output = matches!(options.format, ListFormat::Json | ListFormat::Ndjson);
+    if options.json_version.is_some() && !json_output && options.template.is_none() {
+        return Err(AppError::user(
+            "--json-version only applies to json, ndjson and --template output",
+        )
+        .into());
+    }
+    // Only version 2 carries the long details, so --long picks it for JSON
+    // output unless version 1 was asked for explicitly.
+    let json_version = match options.json_version {
+        Some(version) => version,
+        None if options.long && json_output => JsonVersion::V2,
+        None => JsonVersion::default(),
+    };
+    if options.long && json_output && json_version == JsonVersion::V1 {
+        return Err(AppError::user(
+            "--long with json or ndjson output cannot use --json-version 1",
+        )
+        .into());
+    }
+
+    let template = options
+        .template
+        .as_deref()
+        .map(|text| Template::parse(text, json_version))
+        .transpose()?;
+    let columns = if options.columns.is_empty() {
+        Column::defaults(options.long)
+    } else if json_output {
+        return Err(AppError::user("--columns only applies to table, csv and tsv output").into());
+    } else {
+        options
+            .columns
+            .iter()
+            .map(|name| Column::parse(name))
+            .collect::<std::result::Result<Vec<_>, _>>()?
+    };
+    // Long details shown by the output, as opposed to only sorted on.
+    let show_long = options.long
+        || columns.iter().any(|column| column.needs_long())
+        || template.as_ref().is_some_and(Template::needs_long);
+
+    // A missing default base only hides the base columns; an explicit
+    // --base that does not resolve is an error.
+    let base = if show_long {
+        match common::resolve_base_branch(git, config, &worktrees, options.base.as_deref()) {
+            Ok(base) => Some(base),
+            Err(err) if options.base.is_some() => return Err(err),
+            Err(_) => None,
+        }
+    } else {
+        None
+    };
+
+    // Names are made unique across all worktrees, so filtering does not
+    // change them.
+    let names = common::display_names(&worktrees, &locations.base_dir);
+
+    // Filters that need no Git call run first, so filtered-out worktrees
+    // are never queried.
+    let (selected, selected_names): (Vec<WorktreeInfo>, Vec<String>) = worktrees
+        .iter()
+        .zip(names)
+        .filter(|(info, name)| {
+            let target = FilterTarget {
+                info,
+                name,
+                base_dir: &locations.base_dir,
+                is_current: common::normalize_path(&info.path) == locations.current_worktree,
+            };
+            filters
+                .iter()
+                .all(|filter| filter.matches_worktree(&target))
+        })
+        .map(|(info, name)| (info.clone(), name))
+        .unzip();
+
+    let sort_needs_long = matches!(options.sort, Some(ListSort::LastCommit | ListSort::Ahead));
+    let queries = Queries {
+        status: !options.no_status && (!options.names_only || filter_on_status),
+        upstream: !options.names_only,
+        long: show_long || sort_needs_long,
+    };
+
+    let mut cache = options.cached.then(|| StatusCache::load(repo));
+    let mut rows = build_rows(
+        &selected,
+        selected_names,
+        git,
+        &locations,
+        &queries,
+        base.as_deref(),
+        cache.as_mut(),
+    )?;
+    if let Some(cache) = &mut cache {
+        let paths: Vec<PathBuf> = worktrees
+            .iter()
+            .map(|info| common::normalize_path(&info.path))
+            .collect();
+        cache.retain(&paths);
+        if let Err(err) = cache.save(repo) {
+            warn!("failed to write the status cache: {}", err);
+        }
+    }
+
+    // A worktree whose status could not be read never passes a status
+    // filter.
+    rows.retain(|row| match row.status.as_deref() {
+        Some(status) => filters.iter().all(|filter| filter.matches_status(status)),
+        None => !filter_on_status,
+    });
+    if let Some(sort) = options.sort {
+        sort_rows(&mut rows, sort);
+    }
+    if options.reverse {
+        rows.reverse();
+    }
+
+    let mut stdout = io::stdout().lock();
+    if options.names_only {
+        for row in &rows {
+            writeln!(stdout, "{}", row.name)?;
+        }
+    } else if let Some(template) = &template {
+        for entry in json_entries(&rows, show_long, json_version) {
+            writeln!(stdout, "{}", template.render(&entry)?)?;
+        }
+    } else {
+        match options.format {
+            ListFormat::Table => {
+                table::output_table(&mut stdout, &rows, &columns, table_style(options.color))?
+            }
+            ListFormat::Json => {
+                let json =
+                    serde_json::to_string_pretty(&json_entries(&rows, show_long, json_version))
+                        .context("failed to serialize JSON")?;
+                writeln!(stdout, "{}", json)?;
+            }
+            ListFormat::Ndjson => {
+                for entry in json_entries(&rows, show_long, json_version) {
+                    let line = serde_json::to_string(&entry).context("failed to serialize JSON")?;
+                    writeln!(stdout, "{}", line)?;
+                }
+            }
+            ListFormat::Csv => format::write_delimited(&mut stdout, &rows, &columns, ',')?,
+            ListFormat::Tsv => format::write_delimited(&mut stdout, &rows, &columns, '\t')?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Which per-worktree Git queries a `list` invocation needs.
+struct Queries {
+    status: bool,
+    upstream: bool,
+    /// Divergence and last commit, for `--long` or sorting on them.
+    long: bool,
+}
+
+/// Order rows by `sort`; the sort is stable, so ties and rows without a
+/// value keep the `git worktree list` order, the latter after all others.
+fn sort_rows(rows: &mut [DisplayRow], sort: ListSort) {
+    fn missing_last<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+    let last_commit = |row: &DisplayRow| {
+        row.long
+            .as_ref()
+            .and_then(|long| long.last_commit.as_ref())
+            .map(|commit| Reverse(commit.timestamp))
+    };
+    let ahead = |row: &DisplayRow| {
+        row.long
+            .as_ref()
+            .and_then(|long| long.upstream)
+            .map(|diff| Reverse(diff.ahead))
+    };
+
+    match sort {
+        ListSort::Name => rows.sort_by(|a, b| a.name.cmp(&b.name)),
+        ListSort::Branch => rows.sort_by(|a, b| missing_last(a.branch.as_ref(), b.branch.as_ref())),
+        // Newest commit and most commits ahead first.
+        ListSort::LastCommit => rows.sort_by(|a, b| missing_last(last_commit(a), last_commit(b))),
+        ListSort::Ahead => rows.sort_by(|a, b| missing_last(ahead(a), ahead(b))),
+    }
+}
+
+/// Normalized paths every row is described relative to.
+struct Locations {
+    base_dir: PathBuf,
+    current_worktree: PathBuf,
+    main_root: PathBuf,
+}
+
+/// Everything `list` asks Git about a single worktree.
+struct WorktreeDetails {
+    worktree_status: Option<WorktreeStatus>,
+    /// Whether `worktree_status` came from the status cache.
+    from_cache: bool,
+    upstream: Option<String>,
+    long: Option<LongInfo>,
+}
+
+fn build_rows(
+    worktrees: &[WorktreeInfo],
+    names: Vec<String>,
+    git: &GitRunner,
+    locations: &Locations,
+    queries: &Queries,
+    base: Option<&str>,
+    mut cache: Option<&mut StatusCache>,
+) -> Result<Vec<DisplayRow>> {
+    let jobs: Vec<(&WorktreeInfo, Option<WorktreeStatus>)> = worktrees
+        .iter()
+        .map(|info| {
+            let cached = cache
+                .as_deref()
+                .and_then(|cache| cache.get(&info.path, &info.head));
+            (info, cached)
+        })
+        .collect();
+
+    // The per-worktree Git calls dominate the run time, so they run
+    // concurrently; the rows keep the `git worktree list` order.
+    let details = common::parallel_map(&jobs, |(info, cached)| -> Result<WorktreeDetails> {
+        let abs_path = common::normalize_path(&info.path);
+        let from_cache = cached.is_some();
+        // A prunable worktree's directory may be gone, and Git cannot run
+        // in it; its status and upstream are simply unknown.
+        let missing = !abs_path.exists();
+        let worktree_status = if !queries.status || missing {
+            None
+        } else if let Some(cached) = cached {
+            Some(cached.clone())
+        } else {
+            Some(status::read_status(git, &abs_path)?)
+        };
+        let long = if queries.long {
+            Some(collect_long_info(git, info, base)?)
+        } else {
+            None
+        };
+        let upstream = if queries.upstream && !missing {
+            determine_upstream(git, &abs_path)?
+        } else {
+            None
+        };
+        Ok(WorktreeDetails {
+            worktree_status,
+            from_cache,
+            upstream,
+            long,
+        })
+    });
+
+    let mut rows = Vec::with_capacity(worktrees.len());
+    for ((info, details), name) in worktrees.iter().zip(details).zip(names) {
+        let details = details?;
+        let abs_path = common::normalize_path(&info.path);
+        if let (Some(cache), Some(worktree_status), false) = (
+            cache.as_deref_mut(),
+            &details.worktree_status,
+            details.from_cache,
+        ) {
+            cache.insert(&abs_path, &info.head, worktree_status.clone());
+        }
+        let is_current = abs_path == locations.current_worktree;
+
+        let status = details
+            .worktree_status
+            .as_ref()
+            .map(|worktree_status| status_label(worktree_status).to_string());
+        let branch_display = render_branch(info);
+        let head_short = render_head(&info.head);
+        let path = common::relative_path(&abs_path, &locations.main_root)
+            .to_string_lossy()
+            .into_owned();
+        let abs_path_display = abs_path.to_string_lossy().to_string();
+
+        rows.push(DisplayRow {
+            name,
+            plain_name: common::display_name(info, &locations.base_dir),
+            branch_display,
+            branch: info.branch.clone(),
+            head: head_short,
+            head_full: info.head.clone(),
+            status,
+            worktree_status: details.worktree_status,
+            upstream: details.upstream,
+            path,
+            abs_path: abs_path_display,
+            locked: info.locked.clone(),
+            prunable: info.prunable.clone(),
+            is_main: info.is_main,
+            is_current,
+            is_managed: common::is_managed(info, &locations.base_dir),
+            is_detached: info.is_detached,
+            long: details.long,
+        });
+    }
+
+    Ok(rows)
+}
+
+fn collect_long_info(git: &GitRunner, info: &WorktreeInfo, base: Option<&str>) -> Result<LongInfo> {
+    if info.head.is_empty() || info.head.bytes().all(|b| b == b'0') {
+        return Ok(LongInfo::default());
+    }
+
+    let upstream = match &info.branch {
+        Some(branch) => divergence(git, &format!("{}@{{u}}", branch), &info.head)?,
+        None => None,
+    };
+    let base_diff = match base {
+        Some(base) => divergence(git, base, &info.head)?.map(|diff| (base.to_string(), diff)),
+        None => None,
+    };
+
+    Ok(LongInfo {
+        upstream,
+        base: base_diff,
+        last_commit: last_commit(git, &info.head)?,
+    })
+}
+
+/// Commits `head` has that `other` lacks (ahead) and the reverse (behind);
+/// `None` when `other` does not resolve, e.g. without an upstream.
+fn divergence(git: &GitRunner, other: &str, head: &str) -> Result<Option<Divergence>> {
+    let output = git
+        .run_with_status([
+            "rev-list".to_string(),
+            "--left-right".to_string(),
+            "--count".to_string(),
+            format!("{}...{}", other, head),
+        ])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let mut counts = output
+        .stdout()
+        .split_whitespace()
+        .map(|count| count.parse::<usize>());
+    match (counts.next(), counts.next()) {
+        (Some(Ok(behind)), Some(Ok(ahead))) => Ok(Some(Divergence { ahead, behind })),
+        _ => Ok(None),
+    }
+}
+
+fn last_commit(git: &GitRunner, head: &str) -> Result<Option<LastCommit>> {
+    let output = git
+        .run_with_status(["log", "-1", "--format=%s%x00%an%x00%cr%x00%ct", head])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let line = output.stdout().trim_end_matches('\n');
+    let mut fields = line.split('\0');
+    match (fields.next(), fields.next(), fields.next(), fields.next()) {
+        (Some(subject), Some(author), Some(date), Some(timestamp)) => Ok(Some(LastCommit {
+            subject: subject.to_string(),
+            author: author.to_string(),
+            relative_date: date.to_string(),
+            timestamp: timestamp.trim().parse().unwrap_or(0),
+        })),
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn determine_status(git: &GitRunner, worktree_path: &Path) -> Result<String> {
+    let worktree_status = status::read_status(git, worktree_path)?;
+    Ok(status_label(&worktree_status).to_string())
+}
+
+fn status_label(worktree_status: &WorktreeStatus) -> &'static str {
+    if worktree_status.changes.is_clean() {
+        "clean"
+    } else {
+        "dirty"
+    }
+}
+
+pub(crate) fn determine_upstream(git: &GitRunner, worktree_path: &Path) -> Result<Option<String>> {
+    match git.run_in(
+        worktree_path,
+        ["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+    ) {
+        Ok(output) => {
+            let value = output.stdout().trim();
+            if value.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(value.to_string()))
+            }
+        }
+        Err(GitError::CommandFailed { .. }) => Ok(None),
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
+}
+
+/// Colors and terminal width for the table. Output that is not a terminal
+/// is never elided, so piped tables keep their full paths.
+fn table_style(color: ColorChoice) -> TableStyle {
+    let is_terminal = io::stdout().is_terminal();
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    TableStyle {
+        color: match color {
+            ColorChoice::Auto => is_terminal && !no_color,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        },
+        max_width: is_terminal
+            .then(|| crossterm::terminal::size().ok())
+            .flatten()
+            .map(|(columns, _)| usize::from(columns)),
+    }
+}
+
+fn render_branch(info: &WorktreeInfo) -> String {
+    match &info.branch {
+        Some(branch) => branch.clone(),
+        None => "detached".to_string(),
+    }
+}
+
+/// A lock or prune reason; Git allows both without one.
+fn render_reason(reason: Option<&str>) -> String {
+    match reason {
+        Some("") => "yes".to_string(),
+        Some(reason) => reason.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn render_head(head: &str) -> String {
+    const HEAD_LEN: usize = 8;
+    if head.len() <= HEAD_LEN {
+        head.to_string()
+    } else {
+        head[..HEAD_LEN].to_string()
+    }
+}
+
+fn render_divergence(diff: Option<&Divergence>) -> String {
+    match diff {
+        Some(diff) => format!("+{}/-{}", diff.ahead, diff.behind),
+        None => "-".to_string(),
+    }
+}
+
+/// The JSON objects shared by `json`, `ndjson` and `--template` in the
+/// requested schema version; the long fields are only included when `long`
+/// is set.
+fn json_entries(rows: &[DisplayRow], long: bool, version: JsonVersion) -> Vec<JsonEntry> {
+    rows.iter()
+        .map(|row| {
+            match version {
+                JsonVersion::V1 => JsonEntry::V1(JsonWorktree {
+                    name: row.plain_name.clone(),
+                    branch: row.branch.clone(),
+                    head: row.head.clone(),
+                    status: row.status.clone().unwrap_or_else(|| "-".to_string()),
+                    upstream: row.upstream.clone(),
+                    path: row.plain_name.clone(),
+                    abs_path: row.abs_path.clone(),
+                    is_main: row.is_main,
+                    is_current: row.is_current,
+                }),
+                JsonVersion::V2 => JsonEntry::V2(Box::new(JsonWorktreeV2 {
+                    name: row.name.clone(),
+                    branch: row.branch.clone(),
+                    head: row.head_full.clone(),
+                    head_short: row.head.clone(),
+                    status: row.status.clone(),
+                    changes: row.worktree_status.as_ref().map(|status| status.changes),
+                    operation: row
+                        .worktree_status
+                        .as_ref()
+                        .and_then(|status| status.operation),
+                    upstream: row.upstream.clone(),
+                    path: row.path.clone(),
+                    abs_path: row.abs_path.clone(),
+                    locked: row.locked.clone(),
+                    prunable: row.prunable.clone(),
+                    is_main: row.is_main,
+                    is_current: row.is_current,
+                    is_managed: row.is_managed,
+                    is_detached: row.is_detached,
+                    // Sorting may collect the long details; only --long
+                    // shows them.
+                    long: row.long.as_ref().filter(|_| long).map(JsonLong::from),
+                })),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct DisplayRow {
+    /// Made unique across all worktrees.
+    name: String,
+    /// The display name before disambiguation, as JSON version 1 reports it.
+    plain_name: String,
+    branch_display: String,
+    branch: Option<String>,
+    /// Shortened to 8 characters.
+    head: String,
+    head_full: String,
+    /// `None` with `--no-status`.
+    status: Option<String>,
+    worktree_status: Option<WorktreeStatus>,
+    upstream: Option<String>,
+    /// Relative to the main worktree root (`.` for the main worktree).
+    path: String,
+    abs_path: String,
+    locked: Option<String>,
+    prunable: Option<String>,
+    is_main: bool,
+    is_current: bool,
+    is_managed: bool,
+    is_detached: bool,
+    long: Option<LongInfo>,
+}
+
+/// Details gathered only for `list --long`.
+#[derive(Debug, Default)]
+struct LongInfo {
+    upstream: Option<Divergence>,
+    /// The base branch and the divergence from it.
+    base: Option<(String, Divergence)>,
+    last_commit: Option<LastCommit>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Divergence {
+    ahead: usize,
+    behind: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LastCommit {
+    subject: String,
+    author: String,
+    relative_date: String,
+    timestamp: i64,
+}
+
+/// Version 1 of the JSON schema, the default. Frozen for existing
+/// consumers: `path` repeats the display name, `head` is shortened and
+/// `status` is `"-"` when unknown. New fields only go into version 2.
+#[derive(Debug, Serialize)]
+struct JsonWorktree {
+    name: String,
+    branch: Option<String>,
+    head: String,
+    status: String,
+    upstream: Option<String>,
+    path: String,
+    abs_path: String,
+    is_main: bool,
+    is_current: bool,
+}
+
+/// Version 2 of the JSON schema (`--json-version 2`): unique names, the
+/// full `head`, a real relative `path`, the change counts, the worktree
+/// flags and the `--long` details.
+#[derive(Debug, Serialize)]
+struct JsonWorktreeV2 {
+    name: String,
+    branch: Option<String>,
+    head: String,
+    head_short: String,
+    status: Option<String>,
+    changes: Option<ChangeCounts>,
+    operation: Option<Operation>,
+    upstream: Option<String>,
+    path: String,
+    abs_path: String,
+    locked: Option<String>,
+    prunable: Option<String>,
+    is_main: bool,
+    is_current: bool,
+    is_managed: bool,
+    is_detached: bool,
+    #[serde(flatten)]
+    long: Option<JsonLong>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum JsonEntry {
+    V1(JsonWorktree),
+    V2(Box<JsonWorktreeV2>),
+}
+
+#[derive(Debug, Serialize)]
+struct JsonLong {
+    ahead: Option<usize>,
+    behind: Option<usize>,
+    base_branch: Option<String>,
+    base_ahead: Option<usize>,
+    base_behind: Option<usize>,
+    last_commit: Option<LastCommit>,
+}
+
+impl From<&LongInfo> for JsonLong {
+    fn from(info: &LongInfo) -> Self {
+        Self {
+            ahead: info.upstream.map(|diff| diff.ahead),
+            behind: info.upstream.map(|diff| diff.behind),
+            base_branch: info.base.as_ref().map(|(branch, _)| branch.clone()),
+            base_ahead: info.base.as_ref().map(|(_, diff)| diff.ahead),
+            base_behind: info.base.as_ref().map(|(_, diff)| diff.behind),
+            last_commit: info.last_commit.clone(),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::io::Write;
+
+use anyhow::Result;
+use crossterm::style::{Color, Stylize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::DisplayRow;
+use super::format::Column;
+
+/// Columns that give up width when the table is wider than the terminal, in
+/// the order they shrink.
+const ELIDABLE: [Column; 3] = [Column::AbsPath, Column::Subject, Column::Path];
+
+/// Elided columns keep at least this many cells besides the markers.
+const MIN_ELIDED_WIDTH: usize = 12;
+
+const ELLIPSIS: char = '…';
+
+/// How the table is written to the terminal.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TableStyle {
+    /// Colorize the status and the current-worktree marker.
+    pub(super) color: bool,
+    /// Terminal width to elide paths to; `None` never elides.
+    pub(super) max_width: Option<usize>,
+}
+
+struct Cell {
+    text: String,
+    /// `*` for the current worktree and ` (unmanaged)` for one outside the
+    /// base directory, after the path; kept when the path is elided.
+    marker: &'static str,
+    color: Option<Color>,
+}
+
+impl Cell {
+    fn width(&self) -> usize {
+        self.text.width() + self.marker.width()
+    }
+}
+
+pub(super) fn output_table<W: Write>(
+    writer: &mut W,
+    rows: &[DisplayRow],
+    columns: &[Column],
+    style: TableStyle,
+) -> Result<()> {
+    let headers: Vec<String> = columns.iter().map(|column| column.header()).collect();
+    let mut table: Vec<Vec<Cell>> = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| {
+                    let text = column.value(row).unwrap_or_else(|| "-".to_string());
+                    let color = match (column, text.as_str()) {
+                        (Column::Status, "clean") => Some(Color::Green),
+                        (Column::Status, "dirty") => Some(Color::Yellow),
+                        _ => None,
+                    };
+                    let marker = match (*column == Column::Path, row.is_current, row.is_managed) {
+                        (false, _, _) => "",
+                        (true, true, true) => "*",
+                        (true, true, false) => "* (unmanaged)",
+                        (true, false, false) => " (unmanaged)",
+                        (true, false, true) => "",
+                    };
+                    Cell {
+                        text,
+                        marker,
+                        color,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.width()).collect();
+    // HEAD keeps a fixed width so short and unborn hashes line up.
+    if let Some(head) = columns.iter().position(|column| *column == Column::Head) {
+        widths[head] = widths[head].max(8);
+    }
+    for cells in &table {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    if let Some(max_width) = style.max_width {
+        let minimums: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let markers = table
+                    .iter()
+                    .map(|cells| cells[index].marker.width())
+                    .max()
+                    .unwrap_or(0);
+                MIN_ELIDED_WIDTH.max(column.header().width()) + markers
+            })
+            .collect();
+        shrink_to_fit(columns, &mut widths, &minimums, max_width);
+        for cells in &mut table {
+            for ((column, width), cell) in columns.iter().zip(&widths).zip(cells) {
+                let available = width.saturating_sub(cell.marker.width());
+                if cell.text.width() > available {
+                    cell.text = if *column == Column::Subject {
+                        elide_end(&cell.text, available)
+                    } else {
+                        elide_start(&cell.text, available)
+                    };
+                }
+            }
+        }
+    }
+
+    let header_cells: Vec<Cell> = headers
+        .into_iter()
+        .map(|text| Cell {
+            text,
+            marker: "",
+            color: None,
+        })
+        .collect();
+    write_table_row(writer, &header_cells, &widths, false)?;
+    let rules: Vec<Cell> = widths
+        .iter()
+        .map(|width| Cell {
+            text: "-".repeat(*width),
+            marker: "",
+            color: None,
+        })
+        .collect();
+    write_table_row(writer, &rules, &widths, false)?;
+    for cells in &table {
+        write_table_row(writer, cells, &widths, style.color)?;
+    }
+
+    Ok(())
+}
+
+fn write_table_row<W: Write>(
+    writer: &mut W,
+    cells: &[Cell],
+    widths: &[usize],
+    color: bool,
+) -> Result<()> {
+    let line: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| {
+            // Pad by display width: `{:<width$}` counts chars, which
+            // misaligns wide characters such as Japanese branch names.
+            let padding = " ".repeat(width.saturating_sub(cell.width()));
+            if color {
+                let text = match cell.color {
+                    Some(fg) => cell.text.as_str().with(fg).to_string(),
+                    None => cell.text.clone(),
+                };
+                let marker = match cell.marker.strip_prefix('*') {
+                    Some(rest) => format!("{}{}", "*".with(Color::Cyan).bold(), dim(rest)),
+                    None => dim(cell.marker),
+                };
+                format!("{}{}{}", text, marker, padding)
+            } else {
+                format!("{}{}{}", cell.text, cell.marker, padding)
+            }
+        })
+        .collect();
+    writeln!(writer, "{}", line.join(" "))?;
+    Ok(())
+}
+
+fn dim(text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        text.with(Color::DarkGrey).to_string()
+    }
+}
+
+/// Narrow the elidable columns until the row, separators included, fits in
+/// `max_width`, or they are down to their minimum.
+fn shrink_to_fit(columns: &[Column], widths: &mut [usize], minimums: &[usize], max_width: usize) {
+    let total = widths.iter().sum::<usize>() + widths.len().saturating_sub(1);
+    let mut excess = total.saturating_sub(max_width);
+    for elidable in ELIDABLE {
+        if excess == 0 {
+            break;
+        }
+        let Some(index) = columns.iter().position(|column| *column == elidable) else {
+            continue;
+        };
+        let take = widths[index].saturating_sub(minimums[index]).min(excess);
+        widths[index] -= take;
+        excess -= take;
+    }
+}
+
+/// Keep the end of `text` (the most specific part of a path) within `width`.
+fn elide_start(text: &str, width: usize) -> String {
+    let mut kept = Vec::new();
+    let mut used = ELLIPSIS.width().unwrap_or(1);
+    for ch in text.chars().rev() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > width {
+            break;
+        }
+        used += ch_width;
+        kept.push(ch);
+    }
+    std::iter::once(ELLIPSIS)
+        .chain(kept.into_iter().rev())
+        .collect()
+}
+
+/// Keep the start of `text` within `width`.
+fn elide_end(text: &str, width: usize) -> String {
+    let mut kept = String::new();
+    let mut used = ELLIPSIS.width().unwrap_or(1);
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > width {
+            break;
+        }
+        used += ch_width;
+        kept.push(ch);
+    }
+    kept.push(ELLIPSIS);
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elides_by_display_width() {
+        assert_eq!(elide_start("/src/worktree/feature/auth", 10), "…ture/auth");
+        assert_eq!(elide_end("Fix the login form", 8), "Fix the…");
+        // Each of these characters takes two cells.
+        assert_eq!(elide_start("機能/認証画面", 8), "…証画面");
+        assert_eq!(elide_start("機能/認証画面", 8).width(), 7);
+    }
+
+    #[test]
+    fn shrinks_paths_before_anything_else() {
+        let columns = [Column::Path, Column::Branch, Column::AbsPath];
+        let minimums = [13, 12, 12];
+        let mut widths = [20, 10, 40];
+        shrink_to_fit(&columns, &mut widths, &minimums, 60);
+        assert_eq!(widths, [20, 10, 28]);
+
+        let mut widths = [20, 10, 40];
+        shrink_to_fit(&columns, &mut widths, &minimums, 30);
+        assert_eq!(widths, [13, 10, 12]);
+    }
+
+    #[test]
+    fn pads_wide_characters_by_display_width() {
+        let cells = [
+            Cell {
+                text: "機能".to_string(),
+                marker: "*",
+                color: None,
+            },
+            Cell {
+                text: "x".to_string(),
+                marker: "",
+                color: None,
+            },
+        ];
+        let mut out = Vec::new();
+        write_table_row(&mut out, &cells, &[8, 1], false).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "機能*    x\n");
+    }
+}
@@ -0,0 +1,112 @@
+use std::ffi::OsString;
+use std::io::{self, Write};
+
+use anyhow::Result;
+
+use crate::cli::{LockCommand, UnlockCommand};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::{GitError, GitRunner};
+use crate::git::worktree::list_worktrees;
+use crate::worktree::common;
+use crate::worktree::resolve::Resolver;
+
+pub fn lock(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &LockCommand) -> Result<()> {
+    let target = required_target(cmd.target.as_deref())?;
+    let worktrees = list_worktrees(git)?;
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let info = Resolver::new(&worktrees, &base_dir, repo.repo_name())
+        .include_main(false)
+        .resolve(target)
+        .map_err(anyhow::Error::from)?;
+
+    if let Some(existing) = &info.locked {
+        return Err(AppError::user(format!(
+            "worktree '{}' is already {}",
+            target,
+            common::describe_lock(existing)
+        ))
+        .into());
+    }
+
+    let reason = cmd
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+
+    let mut args: Vec<OsString> = vec!["worktree".into(), "lock".into()];
+    if let Some(reason) = reason {
+        args.push("--reason".into());
+        args.push(reason.into());
+    }
+    args.push(info.path.as_os_str().to_owned());
+    run_lock_command(git, args, "lock")?;
+
+    let mut stdout = io::stdout().lock();
+    match reason {
+        Some(reason) => writeln!(stdout, "Locked worktree '{}': {}", target, reason)?,
+        None => writeln!(stdout, "Locked worktree '{}'", target)?,
+    }
+    Ok(())
+}
+
+pub fn unlock(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &UnlockCommand,
+) -> Result<()> {
+    let target = required_target(cmd.target.as_deref())?;
+    let worktrees = list_worktrees(git)?;
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let info = Resolver::new(&worktrees, &base_dir, repo.repo_name())
+        .include_main(false)
+        .resolve(target)
+        .map_err(anyhow::Error::from)?;
+
+    if info.locked.is_none() {
+        return Err(AppError::user(format!("worktree '{}' is not locked", target)).into());
+    }
+
+    let args: Vec<OsString> = vec![
+        "worktree".into(),
+        "unlock".into(),
+        info.path.as_os_str().to_owned(),
+    ];
+    run_lock_command(git, args, "unlock")?;
+
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "Unlocked worktree '{}'", target)?;
+    Ok(())
+}
+
+fn required_target(target: Option<&str>) -> Result<&str> {
+    target
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::user("worktree name is required").into())
+}
+
+fn run_lock_command(
+    git: &GitRunner,
+    args: Vec<OsString>,
+    action: &str,
+) -> std::result::Result<(), AppError> {
+    match git.run(args) {
+        Ok(_) => Ok(()),
+        Err(GitError::CommandFailed { stderr, .. }) => {
+            let message = stderr.trim();
+            if message.is_empty() {
+                Err(AppError::git(format!(
+                    "git worktree {} failed without error output",
+                    action
+                )))
+            } else {
+                Err(AppError::git(message.to_string()))
+            }
+        }
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
@@ -1,5 +1,15 @@
 pub mod add;
+pub mod backup;
+pub mod clean;
 pub mod common;
+pub mod filter;
+pub mod history;
 pub mod list;
+pub mod lock;
+pub mod picker;
+pub mod prune;
+pub mod relocate;
 pub mod remove;
 pub mod resolve;
+pub mod status;
+pub mod status_cache;
@@ -0,0 +1,400 @@
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, queue, terminal};
+
+use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
+use crate::worktree::{common, list};
+
+/// Rows of worktrees shown at once; the list scrolls past this.
+const MAX_VISIBLE: usize = 10;
+
+/// Whether an interactive picker can be shown.
+///
+/// The picker draws on stderr and reads keys from the terminal, so stdout can
+/// stay captured (as the shell wrappers do for `cd`).
+pub fn is_available() -> bool {
+    io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    branch: String,
+    status: String,
+    path: String,
+}
+
+impl Entry {
+    fn matches(&self, query: &str) -> bool {
+        let haystack = format!("{} {} {}", self.name, self.branch, self.path).to_lowercase();
+        query
+            .to_lowercase()
+            .split_whitespace()
+            .all(|term| common::is_subsequence(term, &haystack))
+    }
+}
+
+/// Let the user choose one of `candidates`; `None` when the picker is
+/// dismissed with Esc or Ctrl-C.
+pub(crate) fn pick<'a>(
+    git: &GitRunner,
+    candidates: &[&'a WorktreeInfo],
+    base_dir: &Path,
+    current: &Path,
+    prompt: &str,
+) -> Result<Option<&'a WorktreeInfo>> {
+    // One `git status` per worktree dominates start-up; run them in parallel.
+    // A prunable worktree's directory may be gone, and Git cannot run in it.
+    let statuses = common::parallel_map(candidates, |info| {
+        let path = common::normalize_path(&info.path);
+        if !path.exists() {
+            return Ok("missing".to_string());
+        }
+        list::determine_status(git, &path)
+    });
+    let mut entries = Vec::with_capacity(candidates.len());
+    for (info, status) in candidates.iter().zip(statuses) {
+        let path = common::normalize_path(&info.path);
+        let mut name = common::display_name(info, base_dir);
+        if path == current {
+            name.push('*');
+        }
+        entries.push(Entry {
+            name,
+            branch: info
+                .branch
+                .clone()
+                .unwrap_or_else(|| "(detached)".to_string()),
+            status: status?,
+            path: path.to_string_lossy().into_owned(),
+        });
+    }
+
+    let choice = run_picker(&entries, prompt)?;
+    Ok(choice.map(|idx| candidates[idx]))
+}
+
+struct State {
+    query: String,
+    matches: Vec<usize>,
+    selected: usize,
+    offset: usize,
+}
+
+impl State {
+    fn new(entries: &[Entry]) -> Self {
+        let mut state = Self {
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            offset: 0,
+        };
+        state.refilter(entries);
+        state
+    }
+
+    fn refilter(&mut self, entries: &[Entry]) {
+        self.matches = filter(entries, &self.query);
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() - 1;
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + MAX_VISIBLE {
+            self.offset = self.selected + 1 - MAX_VISIBLE;
+        }
+    }
+}
+
+enum Action {
+    Continue,
+    Accept,
+    Cancel,
+}
+
+fn handle_key(state: &mut State, entries: &[Entry], key: KeyEvent) -> Action {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => Action::Cancel,
+        KeyCode::Char('c') if ctrl => Action::Cancel,
+        KeyCode::Enter if !state.matches.is_empty() => Action::Accept,
+        KeyCode::Up => {
+            state.move_by(-1);
+            Action::Continue
+        }
+        KeyCode::Char('p') if ctrl => {
+            state.move_by(-1);
+            Action::Continue
+        }
+        KeyCode::Down | KeyCode::Tab => {
+            state.move_by(1);
+            Action::Continue
+        }
+        KeyCode::Char('n') if ctrl => {
+            state.move_by(1);
+            Action::Continue
+        }
+        KeyCode::PageUp => {
+            state.move_by(-(MAX_VISIBLE as isize));
+            Action::Continue
+        }
+        KeyCode::PageDown => {
+            state.move_by(MAX_VISIBLE as isize);
+            Action::Continue
+        }
+        KeyCode::Char('u') if ctrl => {
+            state.query.clear();
+            state.refilter(entries);
+            Action::Continue
+        }
+        KeyCode::Backspace => {
+            if state.query.pop().is_some() {
+                state.refilter(entries);
+            }
+            Action::Continue
+        }
+        KeyCode::Char(ch) if !ctrl => {
+            state.query.push(ch);
+            state.refilter(entries);
+            Action::Continue
+        }
+        _ => Action::Continue,
+    }
+}
+
+fn run_picker(entries: &[Entry], prompt: &str) -> Result<Option<usize>> {
+    let mut stderr = io::stderr().lock();
+    let height = entries.len().min(MAX_VISIBLE) + 1;
+
+    // Make room below the cursor first so redraws never scroll the screen.
+    for _ in 1..height {
+        queue!(stderr, Print("\n"))?;
+    }
+    if height > 1 {
+        queue!(stderr, cursor::MoveUp((height - 1) as u16))?;
+    }
+
+    terminal::enable_raw_mode()?;
+    let result = event_loop(&mut stderr, entries, prompt, height);
+    let cleanup = queue!(
+        stderr,
+        cursor::MoveToColumn(0),
+        terminal::Clear(terminal::ClearType::FromCursorDown)
+    )
+    .and_then(|_| stderr.flush());
+    terminal::disable_raw_mode()?;
+    cleanup?;
+    result
+}
+
+fn event_loop<W: Write>(
+    out: &mut W,
+    entries: &[Entry],
+    prompt: &str,
+    height: usize,
+) -> Result<Option<usize>> {
+    let mut state = State::new(entries);
+    loop {
+        draw(out, entries, &state, prompt, height)?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        match handle_key(&mut state, entries, key) {
+            Action::Continue => {}
+            Action::Accept => return Ok(Some(state.matches[state.selected])),
+            Action::Cancel => return Ok(None),
+        }
+    }
+}
+
+fn draw<W: Write>(
+    out: &mut W,
+    entries: &[Entry],
+    state: &State,
+    prompt: &str,
+    height: usize,
+) -> Result<()> {
+    let width = match terminal::size() {
+        Ok((width, _)) if width > 0 => width as usize,
+        _ => 80,
+    };
+    let lines = render_lines(entries, &state.matches, state.offset, width);
+    let header = truncate(
+        &format!(
+            "{} ({}/{}) > {}",
+            prompt,
+            state.matches.len(),
+            entries.len(),
+            state.query
+        ),
+        width,
+    );
+
+    queue!(
+        out,
+        cursor::MoveToColumn(0),
+        terminal::Clear(terminal::ClearType::FromCursorDown),
+        Print(&header),
+    )?;
+    for (row, line) in lines.iter().enumerate() {
+        queue!(out, Print("\r\n"))?;
+        if state.offset + row == state.selected {
+            queue!(
+                out,
+                SetAttribute(Attribute::Reverse),
+                Print(line),
+                SetAttribute(Attribute::Reset)
+            )?;
+        } else {
+            queue!(out, Print(line))?;
+        }
+    }
+    for _ in lines.len()..height - 1 {
+        queue!(out, Print("\r\n"))?;
+    }
+    if height > 1 {
+        queue!(out, cursor::MoveUp((height - 1) as u16))?;
+    }
+    queue!(out, cursor::MoveToColumn(header.chars().count() as u16))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Indices of the entries matching every whitespace-separated term of
+/// `query`, in their original order.
+fn filter(entries: &[Entry], query: &str) -> Vec<usize> {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.matches(query))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+fn render_lines(entries: &[Entry], matches: &[usize], offset: usize, width: usize) -> Vec<String> {
+    let name_width = entries
+        .iter()
+        .map(|e| e.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let branch_width = entries
+        .iter()
+        .map(|e| e.branch.chars().count())
+        .max()
+        .unwrap_or(0);
+    let status_width = entries
+        .iter()
+        .map(|e| e.status.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    matches
+        .iter()
+        .skip(offset)
+        .take(MAX_VISIBLE)
+        .map(|&idx| {
+            let entry = &entries[idx];
+            let line = format!(
+                "  {:<name_width$}  {:<branch_width$}  {:<status_width$}  {}",
+                entry.name, entry.branch, entry.status, entry.path
+            );
+            truncate(&line, width)
+        })
+        .collect()
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width.saturating_sub(1)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, branch: &str) -> Entry {
+        Entry {
+            name: name.to_string(),
+            branch: branch.to_string(),
+            status: "clean".to_string(),
+            path: format!("/repo/worktree/{}", name),
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn filter_matches_every_term_as_subsequence() {
+        let entries = vec![
+            entry("@", "main"),
+            entry("feature/auth", "feature/auth"),
+            entry("feature/billing", "feature/billing"),
+        ];
+        assert_eq!(filter(&entries, ""), vec![0, 1, 2]);
+        assert_eq!(filter(&entries, "fea"), vec![1, 2]);
+        assert_eq!(filter(&entries, "FTH"), vec![1]);
+        assert_eq!(filter(&entries, "feat bill"), vec![2]);
+        assert!(filter(&entries, "zzz").is_empty());
+    }
+
+    #[test]
+    fn typing_filters_and_arrows_stay_in_range() {
+        let entries: Vec<Entry> = (0..15)
+            .map(|i| entry(&format!("wt-{:02}", i), "b"))
+            .collect();
+        let mut state = State::new(&entries);
+
+        handle_key(&mut state, &entries, key(KeyCode::Up));
+        assert_eq!(state.selected, 0);
+        for _ in 0..12 {
+            handle_key(&mut state, &entries, key(KeyCode::Down));
+        }
+        assert_eq!(state.selected, 12);
+        assert_eq!(state.offset, 3);
+
+        handle_key(&mut state, &entries, key(KeyCode::Char('1')));
+        assert_eq!(state.selected, 0);
+        assert_eq!(state.matches, vec![1, 10, 11, 12, 13, 14]);
+        handle_key(&mut state, &entries, key(KeyCode::PageDown));
+        assert_eq!(state.selected, 5);
+
+        handle_key(&mut state, &entries, key(KeyCode::Backspace));
+        assert_eq!(state.matches.len(), 15);
+        assert!(matches!(
+            handle_key(&mut state, &entries, key(KeyCode::Enter)),
+            Action::Accept
+        ));
+        assert!(matches!(
+            handle_key(
+                &mut state,
+                &entries,
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+            ),
+            Action::Cancel
+        ));
+    }
+
+    #[test]
+    fn rendered_lines_are_aligned_and_fit_the_width() {
+        let entries = vec![entry("@", "main"), entry("feature/auth", "feature/auth")];
+        let lines = render_lines(&entries, &[0, 1], 0, 200);
+        assert_eq!(lines[0].find("clean"), lines[1].find("clean"));
+        assert!(render_lines(&entries, &[1], 0, 20)[0].chars().count() < 20);
+    }
+}
@@ -0,0 +1,331 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::cli::PruneCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::{GitError, GitRunner};
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::common;
+
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &PruneCommand) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
+    let mut stdout = io::stdout().lock();
+
+    let prunable: Vec<&WorktreeInfo> = worktrees
+        .iter()
+        .filter(|info| info.prunable.is_some())
+        .collect();
+
+    if !cmd.dry_run {
+        prune_metadata(git).map_err(anyhow::Error::from)?;
+    }
+
+    let verb = if cmd.dry_run { "Would prune" } else { "Pruned" };
+    for info in &prunable {
+        writeln!(
+            stdout,
+            "{} worktree metadata for {} ({})",
+            verb,
+            info.path.display(),
+            render_reason(info.prunable.as_deref())
+        )?;
+    }
+
+    // Prunable entries are gone from git's point of view once pruned, so a
+    // directory that still exists at such a path counts as orphaned.
+    let registered: Vec<PathBuf> = worktrees
+        .iter()
+        .filter(|info| info.prunable.is_none())
+        .map(|info| common::normalize_path(&info.path))
+        .collect();
+    let orphans = find_orphaned_directories(&base_dir, &registered, repo.common_dir())?;
+
+    if prunable.is_empty() && orphans.is_empty() {
+        writeln!(stdout, "Nothing to prune")?;
+        return Ok(());
+    }
+
+    if orphans.is_empty() {
+        return Ok(());
+    }
+
+    for orphan in &orphans {
+        writeln!(
+            stdout,
+            "Found orphaned directory {} ({})",
+            orphan.path.display(),
+            orphan.kind.describe()
+        )?;
+    }
+
+    if cmd.dry_run {
+        return Ok(());
+    }
+
+    if !cmd.yes {
+        if !common::stdin_is_terminal() {
+            return Err(AppError::user(format!(
+                "refusing to delete {} orphaned director{} without confirmation\n\
+                 Re-run with --yes to delete {}.",
+                orphans.len(),
+                common::plural(orphans.len(), "y", "ies"),
+                common::plural(orphans.len(), "it", "them")
+            ))
+            .into());
+        }
+        stdout.flush()?;
+        if !common::confirm(&format!(
+            "Delete {} orphaned director{}?",
+            orphans.len(),
+            common::plural(orphans.len(), "y", "ies")
+        ))? {
+            writeln!(stdout, "Aborted; no directories were deleted")?;
+            return Ok(());
+        }
+    }
+
+    for orphan in &orphans {
+        fs::remove_dir_all(&orphan.path).with_context(|| {
+            format!(
+                "failed to remove orphaned directory {}",
+                orphan.path.display()
+            )
+        })?;
+        writeln!(
+            stdout,
+            "Removed orphaned directory {}",
+            orphan.path.display()
+        )?;
+        // A nested orphan may have been the only entry of its parent.
+        common::remove_empty_parents(&orphan.path, &base_dir);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OrphanKind {
+    /// Directory with a `.git` file pointing into this repository's
+    /// `worktrees/` metadata that git no longer knows about.
+    StaleCheckout,
+    /// Empty directory, typically a leftover parent of a removed worktree or
+    /// the remains of an interrupted `wtw add`.
+    Empty,
+}
+
+impl OrphanKind {
+    fn describe(self) -> &'static str {
+        match self {
+            OrphanKind::StaleCheckout => "unregistered worktree checkout",
+            OrphanKind::Empty => "empty directory",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OrphanDir {
+    path: PathBuf,
+    kind: OrphanKind,
+}
+
+fn prune_metadata(git: &GitRunner) -> std::result::Result<(), AppError> {
+    match git.run(["worktree", "prune"]) {
+        Ok(_) => Ok(()),
+        Err(GitError::CommandFailed { stderr, .. }) => {
+            let message = stderr.trim();
+            if message.is_empty() {
+                Err(AppError::git(
+                    "git worktree prune failed without error output",
+                ))
+            } else {
+                Err(AppError::git(message.to_string()))
+            }
+        }
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
+
+fn render_reason(reason: Option<&str>) -> &str {
+    match reason {
+        Some(reason) if !reason.is_empty() => reason,
+        _ => "no reason given",
+    }
+}
+
+fn find_orphaned_directories(
+    base_dir: &Path,
+    registered: &[PathBuf],
+    common_dir: &Path,
+) -> Result<Vec<OrphanDir>> {
+    let mut orphans = Vec::new();
+    if !base_dir.is_dir() {
+        return Ok(orphans);
+    }
+
+    let common_dir = common::normalize_path(common_dir);
+    collect_orphans(base_dir, registered, &common_dir, &mut orphans)?;
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(orphans)
+}
+
+fn collect_orphans(
+    dir: &Path,
+    registered: &[PathBuf],
+    common_dir: &Path,
+    orphans: &mut Vec<OrphanDir>,
+) -> Result<()> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("failed to read directory {}", dir.display()))?;
+
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let path = common::normalize_path(&entry.path());
+        if registered.contains(&path) {
+            continue;
+        }
+        if registered.iter().any(|known| known.starts_with(&path)) {
+            collect_orphans(&path, registered, common_dir, orphans)?;
+            continue;
+        }
+
+        let git_entry = path.join(".git");
+        if git_entry.is_dir() {
+            // An independent repository that merely lives under base_dir.
+            continue;
+        }
+        if git_entry.is_file() {
+            if gitfile_points_into(&git_entry, &path, common_dir) {
+                orphans.push(OrphanDir {
+                    path,
+                    kind: OrphanKind::StaleCheckout,
+                });
+            }
+            continue;
+        }
+
+        if fs::read_dir(&path)?.next().is_none() {
+            orphans.push(OrphanDir {
+                path,
+                kind: OrphanKind::Empty,
+            });
+            continue;
+        }
+
+        collect_orphans(&path, registered, common_dir, orphans)?;
+    }
+
+    Ok(())
+}
+
+/// Whether a `.git` file refers to `<common_dir>/worktrees/<name>`.
+fn gitfile_points_into(gitfile: &Path, worktree_dir: &Path, common_dir: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(gitfile) else {
+        return false;
+    };
+    let Some(gitdir) = content
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .map(|value| PathBuf::from(value.trim()))
+    else {
+        return false;
+    };
+
+    let gitdir = if gitdir.is_absolute() {
+        gitdir
+    } else {
+        worktree_dir.join(gitdir)
+    };
+
+    let Some(worktrees_dir) = gitdir.parent() else {
+        return false;
+    };
+    if worktrees_dir
+        .file_name()
+        .map(|name| name != "worktrees")
+        .unwrap_or(true)
+    {
+        return false;
+    }
+
+    worktrees_dir
+        .parent()
+        .map(|parent| common::normalize_path(parent) == common_dir)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn orphan_scan_skips_registered_worktrees_and_their_parents() {
+        let temp = TempDir::new().expect("temp dir");
+        let common_dir = temp.path().join("repo").join(".git");
+        let base_dir = temp.path().join("worktree");
+        let registered = base_dir.join("feature").join("auth");
+        let empty = base_dir.join("feature").join("stale");
+        fs::create_dir_all(&common_dir).expect("common dir");
+        fs::create_dir_all(&registered).expect("registered dir");
+        fs::create_dir_all(&empty).expect("empty dir");
+
+        let orphans = find_orphaned_directories(
+            &base_dir,
+            &[common::normalize_path(&registered)],
+            &common_dir,
+        )
+        .expect("scan");
+
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].path, common::normalize_path(&empty));
+        assert_eq!(orphans[0].kind, OrphanKind::Empty);
+    }
+
+    #[test]
+    fn orphan_scan_only_reports_checkouts_of_this_repository() {
+        let temp = TempDir::new().expect("temp dir");
+        let common_dir = temp.path().join("repo").join(".git");
+        let other_common = temp.path().join("other").join(".git");
+        let base_dir = temp.path().join("worktree");
+        let ours = base_dir.join("ours");
+        let theirs = base_dir.join("theirs");
+        let nested_repo = base_dir.join("nested");
+        fs::create_dir_all(&common_dir).expect("common dir");
+        fs::create_dir_all(&other_common).expect("other common dir");
+        fs::create_dir_all(&ours).expect("ours");
+        fs::create_dir_all(&theirs).expect("theirs");
+        fs::create_dir_all(nested_repo.join(".git")).expect("nested repo");
+        fs::write(
+            ours.join(".git"),
+            format!(
+                "gitdir: {}\n",
+                common_dir.join("worktrees").join("ours").display()
+            ),
+        )
+        .expect("ours gitfile");
+        fs::write(
+            theirs.join(".git"),
+            format!(
+                "gitdir: {}\n",
+                other_common.join("worktrees").join("theirs").display()
+            ),
+        )
+        .expect("theirs gitfile");
+
+        let orphans = find_orphaned_directories(&base_dir, &[], &common_dir).expect("scan");
+
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].path, common::normalize_path(&ours));
+        assert_eq!(orphans[0].kind, OrphanKind::StaleCheckout);
+    }
+}
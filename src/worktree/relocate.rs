@@ -0,0 +1,150 @@
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::MoveCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::{GitError, GitRunner};
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::resolve::Resolver;
+use crate::worktree::{add, common, status_cache};
+
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &MoveCommand) -> Result<()> {
+    let target = cmd.target.trim();
+    if target.is_empty() {
+        return Err(AppError::user("worktree name is required").into());
+    }
+    let new_name = cmd.new_name.trim();
+    if new_name.is_empty() {
+        return Err(AppError::user("new worktree name is required").into());
+    }
+
+    let worktrees = list_worktrees(git)?;
+    let base_dir = config.resolved_base_dir(repo.main_root());
+
+    let target_info = Resolver::new(&worktrees, &base_dir, repo.repo_name())
+        .include_main(false)
+        .resolve(target)
+        .map_err(anyhow::Error::from)?;
+    let old_path = common::normalize_path(&target_info.path);
+
+    if let Some(reason) = &target_info.locked {
+        return Err(AppError::user(format!(
+            "worktree '{}' is {}",
+            target,
+            common::describe_lock(reason)
+        ))
+        .into());
+    }
+
+    if old_path == common::normalize_path(repo.worktree_root()) {
+        return Err(AppError::user(format!(
+            "cannot move the current worktree '{}': {}",
+            target,
+            old_path.display()
+        ))
+        .into());
+    }
+
+    let relative = add::branch_to_relative_path(new_name);
+    if relative.components().next().is_none() {
+        return Err(AppError::user(format!(
+            "worktree name resolves to an empty path: {}",
+            new_name
+        ))
+        .into());
+    }
+    let new_path = base_dir.join(&relative);
+
+    let new_branch = match &target_info.branch {
+        Some(branch) if !cmd.keep_branch && branch != new_name => Some(new_name),
+        _ => None,
+    };
+
+    // The worktree being moved would otherwise conflict with itself.
+    let others: Vec<WorktreeInfo> = worktrees
+        .iter()
+        .filter(|info| common::normalize_path(&info.path) != old_path)
+        .cloned()
+        .collect();
+    add::detect_conflicts(&new_path, new_branch, &others)?;
+
+    let mut stdout = io::stdout().lock();
+
+    if let (Some(old_branch), Some(new_branch)) = (&target_info.branch, new_branch) {
+        rename_branch(git, old_branch, new_branch).map_err(anyhow::Error::from)?;
+        writeln!(
+            stdout,
+            "Renamed branch '{}' to '{}'",
+            old_branch, new_branch
+        )?;
+    }
+
+    add::ensure_parents_exist(&new_path)?;
+    if let Err(err) = move_worktree(git, &old_path, &new_path) {
+        if let (Some(old_branch), Some(new_branch)) = (&target_info.branch, new_branch) {
+            // Put the branch name back so a failed move leaves nothing half-done.
+            let _ = rename_branch(git, new_branch, old_branch);
+        }
+        return Err(err.into());
+    }
+
+    status_cache::invalidate(repo);
+    common::remove_empty_parents(&old_path, &common::normalize_path(&base_dir));
+
+    writeln!(
+        stdout,
+        "Moved worktree '{}' to {}",
+        target,
+        common::normalize_path(&new_path).display()
+    )?;
+
+    Ok(())
+}
+
+fn rename_branch(git: &GitRunner, from: &str, to: &str) -> std::result::Result<(), AppError> {
+    match git.run(["branch", "-m", from, to]) {
+        Ok(_) => Ok(()),
+        Err(GitError::CommandFailed { stderr, .. }) => {
+            let message = stderr.trim();
+            if message.is_empty() {
+                Err(AppError::git(format!(
+                    "failed to rename branch '{}' to '{}'",
+                    from, to
+                )))
+            } else {
+                Err(AppError::git(message.to_string()))
+            }
+        }
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
+
+fn move_worktree(git: &GitRunner, from: &Path, to: &Path) -> std::result::Result<(), AppError> {
+    let args: Vec<OsString> = vec![
+        "worktree".into(),
+        "move".into(),
+        from.as_os_str().to_owned(),
+        to.as_os_str().to_owned(),
+    ];
+
+    match git.run(args) {
+        Ok(_) => Ok(()),
+        Err(GitError::CommandFailed { stderr, .. }) => {
+            let message = stderr.trim();
+            if message.is_empty() {
+                Err(AppError::git(format!(
+                    "git worktree move failed for {} without error output",
+                    from.display()
+                )))
+            } else {
+                Err(AppError::git(message.to_string()))
+            }
+        }
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
@@ -1,6 +1,6 @@
 use std::ffi::OsString;
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
@@ -10,7 +10,9 @@ use crate::error::AppError;
 use crate::git::rev::RepoContext;
 use crate::git::runner::{GitError, GitRunner};
 use crate::git::worktree::{WorktreeInfo, list_worktrees};
-use crate::worktree::common;
+use crate::worktree::history::{self, History};
+use crate::worktree::resolve::Resolver;
+use crate::worktree::{backup, common, list, picker, status_cache};
 
 pub fn run(
     repo: &RepoContext,
@@ -18,116 +20,676 @@ pub fn run(
     config: &Config,
     cmd: &RemoveCommand,
 ) -> Result<()> {
-    let target = cmd
-        .target
-        .as_ref()
+    let targets: Vec<String> = cmd
+        .targets
+        .iter()
         .map(|s| s.trim())
         .filter(|s| !s.is_empty())
-        .ok_or_else(|| AppError::user("worktree name is required"))
-        .map_err(anyhow::Error::from)?
-        .to_string();
+        .map(str::to_string)
+        .collect();
+    // Without a target, let the user pick one when there is a terminal to
+    // ask on; the shell wrappers' --print-fallback probe never prompts.
+    if targets.is_empty() && (cmd.print_fallback || !picker::is_available()) {
+        return Err(AppError::user("worktree name is required").into());
+    }
 
-    if cmd.force_branch && !cmd.with_branch {
+    if cmd.force_branch && !deletes_branch(cmd) {
         return Err(AppError::user("--force-branch requires --with-branch").into());
     }
 
     let worktrees = list_worktrees(git)?;
     let base_dir = config.resolved_base_dir(repo.main_root());
+    let ctx = RemoveContext {
+        repo,
+        git,
+        base_dir: base_dir.clone(),
+        current_path: common::normalize_path(repo.worktree_root()),
+        fallback_dir: config.resolved_fallback_dir(repo.main_root()),
+    };
 
-    let target_info =
-        find_target_worktree(&worktrees, &base_dir, &target).map_err(anyhow::Error::from)?;
+    // A single, literal target keeps the plain error reporting of a
+    // one-off removal; anything else is a batch with per-target results.
+    let batch = targets.len() > 1 || targets.iter().any(|t| common::is_glob(t));
+    let (selected, mut failures, guessed) = if targets.is_empty() {
+        match pick_target(
+            repo,
+            git,
+            &worktrees,
+            &base_dir,
+            repo.repo_name(),
+            &ctx.current_path,
+            cmd.include_unmanaged,
+        )? {
+            Some(selection) => (vec![selection], Vec::new(), Vec::new()),
+            None => {
+                eprintln!("Aborted; no worktrees were removed");
+                return Ok(());
+            }
+        }
+    } else {
+        select_targets(
+            &worktrees,
+            &base_dir,
+            repo.repo_name(),
+            &targets,
+            cmd.include_unmanaged,
+        )
+    };
 
-    let current_path = common::normalize_path(repo.worktree_root());
-    let target_path = common::normalize_path(&target_info.path);
+    // Shell wrappers ask first so they can leave a worktree that is about to
+    // be deleted; a directory in use cannot be removed on Windows.
+    if cmd.print_fallback {
+        if selected
+            .iter()
+            .any(|(_, info)| common::normalize_path(&info.path) == ctx.current_path)
+        {
+            writeln!(io::stdout().lock(), "{}", ctx.fallback_dir.display())?;
+        }
+        return Ok(());
+    }
+    if !batch && let Some((_, err)) = failures.pop() {
+        return Err(err.into());
+    }
+    let total = selected.len() + failures.len();
 
-    if target_path == current_path {
-        return Err(AppError::user(format!(
-            "cannot remove the current worktree '{}': {}",
-            target,
-            target_info.path.display()
-        ))
-        .into());
+    // Refuse locked worktrees before asking anything.
+    let mut removable: Vec<Selected> = Vec::new();
+    for (label, info) in selected {
+        match check_removable(cmd, &label, info) {
+            Ok(()) => removable.push((label, info)),
+            Err(err) if !batch => return Err(err.into()),
+            Err(err) => failures.push((label, err)),
+        }
+    }
+    for (label, err) in &failures {
+        eprintln!("Failed to remove '{}': {}", label, err);
+    }
+
+    if !cmd.yes && !removable.is_empty() && !confirm_removal(git, cmd, &removable, &guessed, batch)?
+    {
+        eprintln!("Aborted; no worktrees were removed");
+        return Ok(());
     }
 
-    let display_path = common::normalize_path(&target_info.path);
-    remove_worktree(git, &target_info.path, cmd.force).map_err(anyhow::Error::from)?;
+    // Backups and branch lookups run Git in the current worktree, so it
+    // goes last; the sort is stable for the others.
+    removable.sort_by_key(|(_, info)| common::normalize_path(&info.path) == ctx.current_path);
 
     let mut stdout = io::stdout().lock();
+
+    for (label, info) in &removable {
+        if let Err(err) = remove_one(&ctx, cmd, label, info, &mut stdout) {
+            if !batch {
+                return Err(err.into());
+            }
+            eprintln!("Failed to remove '{}': {}", label, err);
+            failures.push((label.clone(), err));
+        }
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    let summary = format!("failed to remove {} of {} worktrees", failures.len(), total);
+    Err(failures[0].1.with_message(summary).into())
+}
+
+/// Show what is about to be removed and ask before going ahead.
+///
+/// The prompt appears for a batch (several targets or a glob), for a
+/// worktree only found by fuzzy matching, or when work would be lost.
+/// Without a terminal to ask on, all of these are refused outright so
+/// scripts have to opt in with `--yes`. The summary goes to stderr with the
+/// prompt, since shell wrappers capture stdout.
+fn confirm_removal(
+    git: &GitRunner,
+    cmd: &RemoveCommand,
+    removable: &[Selected],
+    guessed: &[Guess],
+    batch: bool,
+) -> Result<bool> {
+    let losses = removable
+        .iter()
+        .map(|(_, info)| Losses::collect(git, cmd, info))
+        .collect::<std::result::Result<Vec<_>, AppError>>()?;
+    // The fuzzy target each worktree was guessed from, if any.
+    let guesses: Vec<Option<&String>> = removable
+        .iter()
+        .map(|(label, _)| {
+            guessed
+                .iter()
+                .find(|(guess, _)| guess == label)
+                .map(|(_, target)| target)
+        })
+        .collect();
+    let details: Vec<Vec<String>> = guesses
+        .iter()
+        .zip(&losses)
+        .map(|(guess, loss)| {
+            guess
+                .map(|target| format!("matched '{}'", target))
+                .into_iter()
+                .chain(loss.describe())
+                .collect()
+        })
+        .collect();
+    let at_risk = losses.iter().any(|loss| loss.at_risk);
+    let guessing = guesses.iter().any(Option::is_some);
+    let interactive = common::stdin_is_terminal();
+
+    if (at_risk || guessing || batch) && !interactive {
+        let mut message = String::from("refusing to remove without confirmation:");
+        for (((label, _), loss), (guess, details)) in removable
+            .iter()
+            .zip(&losses)
+            .zip(guesses.iter().zip(&details))
+        {
+            if loss.at_risk || guess.is_some() {
+                message.push_str(&format!("\n  {}: {}", label, details.join(", ")));
+            } else if batch {
+                message.push_str(&format!("\n  {}", label));
+            }
+        }
+        message.push_str("\nRe-run with --yes to remove anyway.");
+        return Err(AppError::user(message).into());
+    }
+    if !interactive || (!at_risk && !guessing && !batch) {
+        return Ok(true);
+    }
+
+    let mut stderr = io::stderr().lock();
     writeln!(
-        stdout,
-        "Removed worktree '{}' at {}",
-        target,
-        display_path.display()
+        stderr,
+        "The following worktree{} will be removed:",
+        common::plural(removable.len(), "", "s")
     )?;
+    for ((label, info), details) in removable.iter().zip(&details) {
+        writeln!(
+            stderr,
+            "  {} ({})",
+            label,
+            common::normalize_path(&info.path).display()
+        )?;
+        if !details.is_empty() {
+            writeln!(stderr, "    {}", details.join(", "))?;
+        }
+    }
+    drop(stderr);
+    common::confirm(&format!(
+        "Remove {} worktree{}?",
+        removable.len(),
+        common::plural(removable.len(), "", "s")
+    ))
+}
+
+/// What removing a worktree would take with it.
+#[derive(Debug, Default)]
+struct Losses {
+    dirty_files: usize,
+    unpushed: Option<(usize, String)>,
+    stashes: usize,
+    branch: Option<String>,
+    remote_branch: Option<RemoteBranch>,
+    /// Whether this removal actually discards any of the above.
+    at_risk: bool,
+}
 
-    if cmd.with_branch {
-        if let Some(branch) = &target_info.branch {
-            remove_branch(git, branch, cmd.force_branch).map_err(anyhow::Error::from)?;
-            writeln!(stdout, "Removed branch '{}'", branch)?;
+impl Losses {
+    fn collect(
+        git: &GitRunner,
+        cmd: &RemoveCommand,
+        info: &WorktreeInfo,
+    ) -> std::result::Result<Self, AppError> {
+        // Prunable worktrees have no directory left to inspect.
+        if !info.path.exists() {
+            return Ok(Self::default());
         }
+
+        let status = git
+            .run_in(&info.path, ["status", "--porcelain"])
+            .map_err(|err| AppError::git(err.to_string()))?;
+        let dirty_files = status.stdout().lines().count();
+
+        let upstream = list::determine_upstream(git, &info.path)
+            .map_err(|err| AppError::git(err.to_string()))?;
+        let unpushed = match upstream {
+            Some(upstream) => {
+                let output = git
+                    .run_in(&info.path, ["rev-list", "--count", "@{u}..HEAD"])
+                    .map_err(|err| AppError::git(err.to_string()))?;
+                let count = output.stdout().trim().parse().unwrap_or(0);
+                Some((count, upstream))
+            }
+            None => None,
+        };
+
+        let stashes = match &info.branch {
+            Some(branch) => count_stashes(git, branch)?,
+            None => 0,
+        };
+
+        let remote_branch = match &info.branch {
+            Some(branch) if cmd.with_remote_branch => {
+                find_remote_branch(git, branch)?.filter(|remote| !remote.gone)
+            }
+            _ => None,
+        };
+
+        let unpushed_count = unpushed.as_ref().map_or(0, |(count, _)| *count);
+        // git refuses dirty worktrees without --force, and commits and stashes
+        // only lose their branch with --with-branch.
+        let at_risk = (cmd.force > 0 && dirty_files > 0)
+            || (deletes_branch(cmd) && (unpushed_count > 0 || stashes > 0))
+            || remote_branch.is_some();
+
+        Ok(Self {
+            dirty_files,
+            unpushed,
+            stashes,
+            branch: info.branch.clone(),
+            remote_branch,
+            at_risk,
+        })
     }
 
-    Ok(())
+    fn describe(&self) -> Vec<String> {
+        let mut details = Vec::new();
+        if self.dirty_files > 0 {
+            details.push(format!(
+                "{} uncommitted file{}",
+                self.dirty_files,
+                common::plural(self.dirty_files, "", "s")
+            ));
+        }
+        if let Some((count, upstream)) = &self.unpushed
+            && *count > 0
+        {
+            details.push(format!(
+                "{} commit{} not pushed to {}",
+                count,
+                common::plural(*count, "", "s"),
+                upstream
+            ));
+        }
+        if self.stashes > 0 {
+            details.push(format!(
+                "{} stash{} on {}",
+                self.stashes,
+                common::plural(self.stashes, "", "es"),
+                self.branch.as_deref().unwrap_or("this branch")
+            ));
+        }
+        if let Some(remote) = &self.remote_branch {
+            details.push(format!("remote branch '{}' will be deleted", remote));
+        }
+        details
+    }
 }
 
-fn find_target_worktree<'a>(
+fn deletes_branch(cmd: &RemoveCommand) -> bool {
+    cmd.with_branch || cmd.with_remote_branch
+}
+
+/// The branch an upstream points at on its remote.
+#[derive(Debug)]
+struct RemoteBranch {
+    remote: String,
+    /// Full ref on the remote side, e.g. `refs/heads/feature/auth`.
+    reference: String,
+    /// The remote-tracking ref says the branch no longer exists there.
+    gone: bool,
+}
+
+impl std::fmt::Display for RemoteBranch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self
+            .reference
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&self.reference);
+        write!(f, "{}/{}", self.remote, name)
+    }
+}
+
+/// Look up the remote side of `branch`'s upstream. Branches tracking another
+/// local branch (remote `.`) have nothing to delete remotely.
+fn find_remote_branch(
+    git: &GitRunner,
+    branch: &str,
+) -> std::result::Result<Option<RemoteBranch>, AppError> {
+    let output = git
+        .run_in(
+            working_dir(git),
+            [
+                "for-each-ref".to_string(),
+                "--format=%(upstream:remotename)%00%(upstream:remoteref)%00%(upstream:track)"
+                    .to_string(),
+                format!("refs/heads/{}", branch),
+            ],
+        )
+        .map_err(|err| AppError::git(err.to_string()))?;
+
+    let line = output.stdout().trim_end_matches(['\r', '\n']);
+    let mut fields = line.split('\0');
+    let remote = fields.next().unwrap_or("");
+    let reference = fields.next().unwrap_or("");
+    let track = fields.next().unwrap_or("");
+    if remote.is_empty() || remote == "." || reference.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(RemoteBranch {
+        remote: remote.to_string(),
+        reference: reference.to_string(),
+        gone: track == "[gone]",
+    }))
+}
+
+fn delete_remote_branch(
+    git: &GitRunner,
+    remote: &RemoteBranch,
+) -> std::result::Result<(), AppError> {
+    match git.run_in(
+        working_dir(git),
+        [
+            "push",
+            remote.remote.as_str(),
+            "--delete",
+            remote.reference.as_str(),
+        ],
+    ) {
+        Ok(_) => Ok(()),
+        Err(GitError::CommandFailed { stderr, .. }) => {
+            let message = stderr.trim();
+            if message.is_empty() {
+                Err(AppError::git(format!(
+                    "failed to delete remote branch '{}'",
+                    remote
+                )))
+            } else {
+                Err(AppError::git(format!(
+                    "failed to delete remote branch '{}': {}",
+                    remote, message
+                )))
+            }
+        }
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
+
+fn count_stashes(git: &GitRunner, branch: &str) -> std::result::Result<usize, AppError> {
+    let output = git
+        .run_in(working_dir(git), ["stash", "list", "--format=%gs"])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    Ok(output
+        .stdout()
+        .lines()
+        .filter(|subject| stash_references_branch(subject, branch))
+        .count())
+}
+
+/// Stash subjects read `WIP on <branch>: ...` or `On <branch>: ...`.
+fn stash_references_branch(subject: &str, branch: &str) -> bool {
+    let rest = subject
+        .strip_prefix("WIP on ")
+        .or_else(|| subject.strip_prefix("On "));
+    rest.and_then(|rest| rest.strip_prefix(branch))
+        .is_some_and(|rest| rest.starts_with(':'))
+}
+
+struct RemoveContext<'a> {
+    repo: &'a RepoContext,
+    git: &'a GitRunner,
+    base_dir: PathBuf,
+    current_path: PathBuf,
+    fallback_dir: PathBuf,
+}
+
+/// A resolved worktree together with the name used to report on it.
+type Selected<'a> = (String, &'a WorktreeInfo);
+/// A target that could not be resolved or removed, with the reason.
+type Failure = (String, AppError);
+/// The label of a worktree only found by fuzzy matching, and the target it
+/// was guessed from.
+type Guess = (String, String);
+
+/// Let the user choose the worktree to remove in the interactive picker,
+/// most frecent first; `None` when the picker is dismissed.
+///
+/// The current worktree is not offered: the shell wrappers only move out of
+/// it when `--print-fallback` names it, which needs an explicit target.
+fn pick_target<'a>(
+    repo: &RepoContext,
+    git: &GitRunner,
     worktrees: &'a [WorktreeInfo],
-    base_dir: &Path,
-    target: &str,
-) -> std::result::Result<&'a WorktreeInfo, AppError> {
-    let mut available = Vec::new();
-    let mut found: Option<&WorktreeInfo> = None;
+    base_dir: &'a Path,
+    repo_name: &'a str,
+    current_path: &Path,
+    include_unmanaged: bool,
+) -> Result<Option<Selected<'a>>> {
+    let mut candidates: Vec<&WorktreeInfo> = Resolver::new(worktrees, base_dir, repo_name)
+        .include_main(false)
+        .include_unmanaged(include_unmanaged)
+        .candidates()
+        .filter(|info| common::normalize_path(&info.path) != current_path)
+        .collect();
+    if candidates.is_empty() {
+        return Err(AppError::user("no worktrees to remove").into());
+    }
+    History::load(repo)
+        .frecency(history::now())
+        .sort(&mut candidates);
 
-    for info in worktrees {
-        if info.is_main {
-            continue;
+    let picked = picker::pick(git, &candidates, base_dir, current_path, "remove")?;
+    Ok(picked.map(|info| (common::display_name(info, base_dir), info)))
+}
+
+/// Resolve every target to worktrees, expanding glob patterns against
+/// display names and branches. Returns the unique selections (labelled with
+/// the name used in messages), the targets that could not be resolved and
+/// the selections only found by fuzzy matching, which are confirmed before
+/// anything is removed.
+fn select_targets<'a>(
+    worktrees: &'a [WorktreeInfo],
+    base_dir: &'a Path,
+    repo_name: &'a str,
+    targets: &[String],
+    include_unmanaged: bool,
+) -> (Vec<Selected<'a>>, Vec<Failure>, Vec<Guess>) {
+    let resolver = Resolver::new(worktrees, base_dir, repo_name)
+        .include_main(false)
+        .include_unmanaged(include_unmanaged)
+        .fuzzy(true);
+    let mut selected: Vec<Selected<'a>> = Vec::new();
+    let mut failures = Vec::new();
+    let mut guessed = Vec::new();
+
+    let mut push = |label: String, info: &'a WorktreeInfo| {
+        if !selected.iter().any(|(_, known)| known.path == info.path) {
+            selected.push((label, info));
         }
-        if !common::is_managed(info, base_dir) {
+    };
+
+    for target in targets {
+        if !common::is_glob(target) {
+            match resolver.resolve_match(target) {
+                Ok((info, false)) => push(target.clone(), info),
+                Ok((info, true)) => {
+                    let label = common::display_name(info, base_dir);
+                    guessed.push((label.clone(), target.clone()));
+                    push(label, info);
+                }
+                Err(err) if !include_unmanaged => {
+                    // Point at the flag when the target exists outside base_dir.
+                    let err = match resolver.include_unmanaged(true).resolve(target) {
+                        Ok(info) => AppError::user(format!(
+                            "worktree '{}' not found under base_dir {} (it is registered at {})\nPass --include-unmanaged to remove it anyway.",
+                            target,
+                            common::normalize_path(base_dir).display(),
+                            common::normalize_path(&info.path).display()
+                        )),
+                        Err(_) => err,
+                    };
+                    failures.push((target.clone(), err));
+                }
+                Err(err) => failures.push((target.clone(), err)),
+            }
             continue;
         }
 
-        if info.branch.as_deref() == Some(target) {
-            found = Some(info);
-            break;
+        let mut matched = false;
+        for info in worktrees {
+            if info.is_main || (!include_unmanaged && !common::is_managed(info, base_dir)) {
+                continue;
+            }
+            let name = common::display_name(info, base_dir);
+            let branch_matches = info
+                .branch
+                .as_deref()
+                .map(|branch| common::glob_match(target, branch))
+                .unwrap_or(false);
+            if branch_matches || common::glob_match(target, &name) {
+                matched = true;
+                push(name, info);
+            }
         }
-
-        if info
-            .path
-            .file_name()
-            .map(|name| name.to_string_lossy() == target)
-            .unwrap_or(false)
-        {
-            found = Some(info);
-            break;
+        if !matched {
+            failures.push((
+                target.clone(),
+                AppError::user(format!("no worktrees match '{}'", target)),
+            ));
         }
+    }
 
-        let name = common::display_name(info, base_dir);
-        if name == target {
-            found = Some(info);
-            break;
+    (selected, failures, guessed)
+}
+
+fn remove_one<W: Write>(
+    ctx: &RemoveContext,
+    cmd: &RemoveCommand,
+    target: &str,
+    target_info: &WorktreeInfo,
+    stdout: &mut W,
+) -> std::result::Result<(), AppError> {
+    let git = ctx.git;
+    let target_path = common::normalize_path(&target_info.path);
+
+    // Deleting the local branch also drops its upstream configuration, so
+    // look the remote branch up first.
+    let remote_branch = match &target_info.branch {
+        Some(branch) if cmd.with_remote_branch => find_remote_branch(git, branch)?,
+        _ => None,
+    };
+
+    // Without --force git refuses dirty worktrees by itself; with it, keep a
+    // copy of the work that is about to be discarded.
+    let backup = if cmd.force > 0 && is_dirty(git, &target_path)? {
+        let name = common::display_name(target_info, &ctx.base_dir);
+        Some(backup::create(
+            ctx.repo,
+            git,
+            &name,
+            &target_path,
+            target_info.branch.as_deref(),
+        )?)
+    } else {
+        None
+    };
+
+    remove_worktree(git, &target_info.path, cmd.force.min(2))?;
+    writeln!(
+        stdout,
+        "Removed worktree '{}' at {}",
+        target,
+        target_path.display()
+    )
+    .map_err(AppError::internal_from)?;
+    if let Some(backup) = &backup {
+        backup::report(stdout, backup).map_err(AppError::internal_from)?;
+    }
+
+    if deletes_branch(cmd)
+        && let Some(branch) = &target_info.branch
+    {
+        remove_branch(git, branch, cmd.force_branch)?;
+        writeln!(stdout, "Removed branch '{}'", branch).map_err(AppError::internal_from)?;
+
+        match &remote_branch {
+            Some(remote) if remote.gone => {
+                writeln!(stdout, "Remote branch '{}' is already gone", remote)
+            }
+            Some(remote) => {
+                delete_remote_branch(git, remote)?;
+                writeln!(stdout, "Deleted remote branch '{}'", remote)
+            }
+            None if cmd.with_remote_branch => writeln!(
+                stdout,
+                "Branch '{}' has no remote upstream; nothing to delete remotely",
+                branch
+            ),
+            None => Ok(()),
         }
+        .map_err(AppError::internal_from)?;
+    }
 
-        available.push(name);
+    if target_path == ctx.current_path {
+        writeln!(
+            stdout,
+            "The current worktree was removed; continue in {}",
+            ctx.fallback_dir.display()
+        )
+        .map_err(AppError::internal_from)?;
     }
 
-    if let Some(info) = found {
-        return Ok(info);
+    Ok(())
+}
+
+fn check_removable(
+    cmd: &RemoveCommand,
+    target: &str,
+    target_info: &WorktreeInfo,
+) -> std::result::Result<(), AppError> {
+    // Mirror git: a locked worktree needs --force twice.
+    if let Some(reason) = &target_info.locked
+        && cmd.force < 2
+    {
+        return Err(AppError::user(format!(
+            "cannot remove worktree '{}': it is {}\nRun 'wtw unlock {}' or pass --force twice to remove it anyway.",
+            target,
+            common::describe_lock(reason),
+            target
+        )));
     }
 
-    Err(worktree_not_found(target, available))
+    Ok(())
 }
 
-fn remove_worktree(git: &GitRunner, path: &Path, force: bool) -> std::result::Result<(), AppError> {
+fn is_dirty(git: &GitRunner, path: &Path) -> std::result::Result<bool, AppError> {
+    list::determine_status(git, path)
+        .map(|status| status != "clean")
+        .map_err(|err| AppError::git(err.to_string()))
+}
+
+/// Run `git worktree remove`, passing `--force` `force` times (git needs it
+/// twice to remove a locked worktree).
+pub(crate) fn remove_worktree(
+    git: &GitRunner,
+    path: &Path,
+    force: u8,
+) -> std::result::Result<(), AppError> {
     let mut args: Vec<OsString> = Vec::new();
     args.push("worktree".into());
     args.push("remove".into());
-    if force {
+    for _ in 0..force {
         args.push("--force".into());
     }
     args.push(path.to_string_lossy().into_owned().into());
 
-    match git.run(args) {
-        Ok(_) => Ok(()),
+    match git.run_in(working_dir(git), args) {
+        Ok(_) => {
+            status_cache::invalidate(git.repo());
+            Ok(())
+        }
         Err(GitError::CommandFailed { stderr, .. }) => {
             let message = stderr.trim();
             if message.is_empty() {
@@ -143,9 +705,13 @@ fn remove_worktree(git: &GitRunner, path: &Path, force: bool) -> std::result::Re
     }
 }
 
-fn remove_branch(git: &GitRunner, branch: &str, force: bool) -> std::result::Result<(), AppError> {
+pub(crate) fn remove_branch(
+    git: &GitRunner,
+    branch: &str,
+    force: bool,
+) -> std::result::Result<(), AppError> {
     let flag = if force { "-D" } else { "-d" };
-    match git.run(["branch", flag, branch]) {
+    match git.run_in(working_dir(git), ["branch", flag, branch]) {
         Ok(_) => Ok(()),
         Err(GitError::CommandFailed { stderr, .. }) => {
             let message = stderr.trim();
@@ -162,17 +728,34 @@ fn remove_branch(git: &GitRunner, branch: &str, force: bool) -> std::result::Res
     }
 }
 
-fn worktree_not_found(target: &str, mut available: Vec<String>) -> AppError {
-    let suggestion = if available.is_empty() {
-        "Run 'wtw list' to see available worktrees.".to_string()
+/// Directory to run git in once the current worktree may have been removed.
+fn working_dir(git: &GitRunner) -> &Path {
+    let root = git.repo().worktree_root();
+    if root.exists() {
+        root
     } else {
-        available.sort();
-        available.dedup();
-        format!(
-            "Available worktrees: {}\nRun 'wtw list' to see available worktrees.",
-            available.join(", ")
-        )
-    };
+        git.repo().main_root()
+    }
+}
 
-    AppError::user(format!("worktree '{}' not found\n{}", target, suggestion))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stash_references_branch_matches_exact_branch_only() {
+        assert!(stash_references_branch(
+            "WIP on feature/a: 1234567 msg",
+            "feature/a"
+        ));
+        assert!(stash_references_branch("On feature/a: saved", "feature/a"));
+        assert!(!stash_references_branch(
+            "On feature/ab: saved",
+            "feature/a"
+        ));
+        assert!(!stash_references_branch(
+            "WIP on main: 1234567 msg",
+            "feature/a"
+        ));
+    }
 }
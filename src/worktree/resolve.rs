@@ -1,177 +1,517 @@
+use std::env;
 use std::io::{self, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use anyhow::Result;
+use tracing::warn;
 
+use crate::cli::{CdCommand, PathCommand};
 use crate::config::Config;
 use crate::error::AppError;
 use crate::git::rev::RepoContext;
 use crate::git::runner::GitRunner;
 use crate::git::worktree::{WorktreeInfo, list_worktrees};
-use crate::worktree::common;
-
-pub fn run(
-    repo: &RepoContext,
-    git: &GitRunner,
-    config: &Config,
-    target: Option<String>,
-) -> Result<()> {
-    let target = target
-        .ok_or_else(|| AppError::user("worktree name is required"))
-        .map_err(anyhow::Error::from)?;
-    let target = sanitize_target(&target);
-    if target.is_empty() {
+use crate::worktree::history::{self, Frecency, History};
+use crate::worktree::{common, picker};
+
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &CdCommand) -> Result<()> {
+    let target = sanitize_target(cmd.target.as_deref().unwrap_or_default());
+    if target.is_empty() && !picker::is_available() {
         return Err(AppError::user("worktree name is required").into());
     }
 
     let worktrees = list_worktrees(git)?;
     let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
     let repo_name = repo.repo_name().to_string();
+    let current = common::normalize_path(repo.worktree_root());
+    let history = History::load(repo);
+    let frecency = history.frecency(history::now());
+    let (target, inner) = split_target(&target);
+
+    let resolved = if target.is_empty() {
+        let mut candidates: Vec<&WorktreeInfo> = Resolver::new(&worktrees, &base_dir, &repo_name)
+            .candidates()
+            .collect();
+        frecency.sort(&mut candidates);
+        match picker::pick(git, &candidates, &base_dir, &current, "cd")? {
+            Some(info) => info.path.clone(),
+            None => return Err(AppError::user("no worktree selected").into()),
+        }
+    } else if target == "-" {
+        let resolver = Resolver::new(&worktrees, &base_dir, &repo_name);
+        history
+            .previous(&current, |path| {
+                resolver
+                    .candidates()
+                    .any(|info| common::normalize_path(&info.path) == path)
+            })
+            .ok_or_else(|| AppError::user("no previous worktree in history"))?
+    } else {
+        resolve_path(&worktrees, &base_dir, &repo_name, target, &frecency)
+            .map_err(anyhow::Error::from)?
+    };
+
+    let normalized = common::normalize_path(&resolved);
+    let keep_subdir = cmd.keep_subdir || (config.defaults.cd_keep_subdir && !cmd.no_keep_subdir);
+    let destination = if let Some(inner) = inner {
+        let dir = join_inner(&normalized, inner)?;
+        if !dir.is_dir() {
+            return Err(AppError::user(format!(
+                "'{}' is not a directory in worktree '{}'",
+                inner, target
+            ))
+            .into());
+        }
+        common::normalize_path(&dir)
+    } else if keep_subdir {
+        env::current_dir()
+            .map(|cwd| same_subdir(&common::normalize_path(&cwd), &current, &normalized))
+            .unwrap_or_else(|_| normalized.clone())
+    } else {
+        normalized.clone()
+    };
 
-    let resolved = resolve_path(&worktrees, &base_dir, &repo_name, &target)
-        .ok_or_else(|| worktree_not_found(&target, &worktrees, &base_dir, &repo_name))
-        .map_err(anyhow::Error::from)?;
+    // Only a destination that passed the checks above counts as a visit.
+    if normalized != current
+        && let Err(err) = history::record(repo, &current, &normalized)
+    {
+        warn!("failed to record worktree history: {}", err);
+    }
 
     let mut stdout = io::stdout().lock();
-    let normalized = common::normalize_path(&resolved);
-    writeln!(stdout, "{}", normalized.display())?;
+    writeln!(stdout, "{}", destination.display())?;
+    Ok(())
+}
+
+/// `wtw path`: print a worktree root, or a path inside it given as
+/// `worktree:path`.
+pub fn path(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &PathCommand) -> Result<()> {
+    let target = sanitize_target(&cmd.target);
+    let (target, inner) = split_target(&target);
+    if target.is_empty() {
+        return Err(AppError::user("worktree name is required").into());
+    }
+
+    let worktrees = list_worktrees(git)?;
+    let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
+    let frecency = History::load(repo).frecency(history::now());
+    let root = common::normalize_path(
+        &resolve_path(&worktrees, &base_dir, repo.repo_name(), target, &frecency)
+            .map_err(anyhow::Error::from)?,
+    );
+
+    let mut resolved = match inner {
+        Some(inner) => common::normalize_path(&join_inner(&root, inner)?),
+        None => root,
+    };
+    if cmd.relative {
+        let cwd = common::normalize_path(&env::current_dir()?);
+        resolved = common::relative_path(&resolved, &cwd);
+    }
+
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", resolved.display())?;
     Ok(())
 }
 
+/// Split `worktree:path` into its parts.
+///
+/// The last `:` separates them, except right after a drive letter so that
+/// `C:\repo\worktree` is still taken as a plain worktree path.
+pub(crate) fn split_target(target: &str) -> (&str, Option<&str>) {
+    match target.rsplit_once(':') {
+        Some((worktree, _))
+            if worktree.len() == 1 && worktree.chars().all(|c| c.is_ascii_alphabetic()) =>
+        {
+            (target, None)
+        }
+        Some((worktree, inner)) => (worktree, Some(inner)),
+        None => (target, None),
+    }
+}
+
+/// Join a `worktree:path` path onto the worktree root, refusing anything
+/// that could leave the worktree (absolute paths, drive prefixes, `..`).
+fn join_inner(root: &Path, inner: &str) -> std::result::Result<PathBuf, AppError> {
+    let inner_path = Path::new(inner);
+    let escapes = inner_path.components().any(|component| {
+        matches!(
+            component,
+            Component::RootDir | Component::Prefix(_) | Component::ParentDir
+        )
+    });
+    if escapes {
+        return Err(AppError::user(format!(
+            "path '{}' must be relative to the worktree and stay inside it",
+            inner
+        )));
+    }
+    Ok(root.join(inner_path))
+}
+
+/// The directory in `target_root` at the same relative position as `cwd`
+/// within `current_root`, or `target_root` itself if there is none.
+fn same_subdir(cwd: &Path, current_root: &Path, target_root: &Path) -> PathBuf {
+    match cwd.strip_prefix(current_root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => {
+            let candidate = target_root.join(relative);
+            if candidate.is_dir() {
+                candidate
+            } else {
+                target_root.to_path_buf()
+            }
+        }
+        _ => target_root.to_path_buf(),
+    }
+}
+
 fn sanitize_target(target: &str) -> String {
     target.trim().trim_end_matches('*').to_string()
 }
 
-fn resolve_path(
+/// Resolve a `cd` target: any managed worktree, including the main one.
+pub(crate) fn resolve_path(
     worktrees: &[WorktreeInfo],
     base_dir: &Path,
     repo_name: &str,
     target: &str,
-) -> Option<PathBuf> {
-    for info in worktrees {
-        let path = info.path.clone();
+    frecency: &Frecency,
+) -> std::result::Result<PathBuf, AppError> {
+    Resolver::new(worktrees, base_dir, repo_name)
+        .fuzzy(true)
+        .rank_by(frecency)
+        .resolve(target)
+        .map(|info| info.path.clone())
+}
 
-        if matches_main(info, repo_name, target) {
-            return Some(path);
-        }
+/// Name-to-worktree matching shared by every command that takes a worktree.
+///
+/// Exact matches win (main worktree aliases, branch, display name, directory
+/// name), then a filesystem path. With [`Resolver::fuzzy`], a unique
+/// case-insensitive prefix of a name comes next, then a unique subsequence.
+/// Several matches at the fuzzy stages are an error listing the candidates,
+/// unless `cd` history singles one out.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Resolver<'a> {
+    worktrees: &'a [WorktreeInfo],
+    base_dir: &'a Path,
+    repo_name: &'a str,
+    include_main: bool,
+    include_unmanaged: bool,
+    fuzzy: bool,
+    frecency: Option<&'a Frecency>,
+}
 
-        if !common::is_managed(info, base_dir) {
-            continue;
+impl<'a> Resolver<'a> {
+    pub(crate) fn new(
+        worktrees: &'a [WorktreeInfo],
+        base_dir: &'a Path,
+        repo_name: &'a str,
+    ) -> Self {
+        Self {
+            worktrees,
+            base_dir,
+            repo_name,
+            include_main: true,
+            include_unmanaged: false,
+            fuzzy: false,
+            frecency: None,
         }
+    }
+
+    /// Whether the main worktree can be a result (`cd` yes, `remove` no).
+    pub(crate) fn include_main(mut self, include: bool) -> Self {
+        self.include_main = include;
+        self
+    }
 
-        if matches_branch(info, target) {
-            return Some(path);
+    /// Whether worktrees outside `base_dir` can be a result.
+    pub(crate) fn include_unmanaged(mut self, include: bool) -> Self {
+        self.include_unmanaged = include;
+        self
+    }
+
+    /// Also accept prefixes and subsequences of names. Navigation opts in,
+    /// and `remove`, which confirms a guessed worktree before deleting it
+    /// (see [`Resolver::resolve_match`]); `move` and `lock` take exact names
+    /// so that a typo cannot select an unrelated worktree.
+    pub(crate) fn fuzzy(mut self, fuzzy: bool) -> Self {
+        self.fuzzy = fuzzy;
+        self
+    }
+
+    /// Break fuzzy ties in favour of the most frecent worktree.
+    pub(crate) fn rank_by(mut self, frecency: &'a Frecency) -> Self {
+        self.frecency = Some(frecency);
+        self
+    }
+
+    pub(crate) fn resolve(&self, target: &str) -> std::result::Result<&'a WorktreeInfo, AppError> {
+        self.resolve_match(target).map(|(info, _)| info)
+    }
+
+    /// Like [`Resolver::resolve`], also telling whether the worktree was
+    /// only found by the fuzzy stages, i.e. guessed.
+    pub(crate) fn resolve_match(
+        &self,
+        target: &str,
+    ) -> std::result::Result<(&'a WorktreeInfo, bool), AppError> {
+        if let Some(info) = self
+            .candidates()
+            .find(|info| self.matches_exactly(info, target))
+        {
+            return Ok((info, false));
         }
 
-        if matches_display_name(info, base_dir, target) {
-            return Some(path);
+        let target_path = common::normalize_path(Path::new(target));
+        if let Some(info) = self
+            .worktrees
+            .iter()
+            .find(|info| common::normalize_path(&info.path) == target_path)
+        {
+            if info.is_main && !self.include_main {
+                return Err(AppError::user(format!(
+                    "'{}' is the main worktree, which cannot be targeted",
+                    target
+                )));
+            }
+            if self.in_scope(info) {
+                return Ok((info, false));
+            }
         }
 
-        if matches_directory_name(info, target) {
-            return Some(path);
+        let matches = self.fuzzy_matches(target);
+        if !self.fuzzy {
+            return Err(self.not_found_near(target, &matches));
+        }
+        match matches.as_slice() {
+            [] => Err(self.not_found(target)),
+            [info] => Ok((info, true)),
+            _ => self
+                .most_frecent(&matches)
+                .map(|info| (info, true))
+                .ok_or_else(|| self.ambiguous(target, &matches)),
         }
     }
 
-    None
-}
-
-fn matches_main(info: &WorktreeInfo, repo_name: &str, target: &str) -> bool {
-    if !info.is_main {
-        return false;
+    /// Worktrees whose names start with `target`, or failing that contain
+    /// its characters in order, ignoring case.
+    fn fuzzy_matches(&self, target: &str) -> Vec<&'a WorktreeInfo> {
+        let needle = target.to_lowercase();
+        for fuzzy_match in [is_prefix, common::is_subsequence] {
+            let matches: Vec<&'a WorktreeInfo> = self
+                .candidates()
+                .filter(|info| {
+                    self.names(info)
+                        .iter()
+                        .any(|name| fuzzy_match(&needle, &name.to_lowercase()))
+                })
+                .collect();
+            if !matches.is_empty() {
+                return matches;
+            }
+        }
+        Vec::new()
     }
 
-    if target == "@" || target.eq_ignore_ascii_case("root") {
-        return true;
+    /// Worktrees this resolver may return, in `git worktree list` order.
+    pub(crate) fn candidates(&self) -> impl Iterator<Item = &'a WorktreeInfo> + '_ {
+        self.worktrees.iter().filter(|info| self.in_scope(info))
     }
 
-    if target.eq_ignore_ascii_case(repo_name) {
-        return true;
+    fn in_scope(&self, info: &WorktreeInfo) -> bool {
+        if info.is_main {
+            return self.include_main;
+        }
+        self.include_unmanaged || common::is_managed(info, self.base_dir)
     }
 
-    if let Some(branch) = &info.branch {
-        if branch == target {
+    fn matches_exactly(&self, info: &WorktreeInfo, target: &str) -> bool {
+        if info.is_main && matches_main_alias(self.repo_name, target) {
+            return true;
+        }
+        if info.branch.as_deref() == Some(target) {
+            return true;
+        }
+        if !info.is_main && common::display_name(info, self.base_dir) == target {
             return true;
         }
+        // The directory name, or the trailing directories `list` shows for
+        // unmanaged worktrees whose names collide (`a/tmp`).
+        let target = Path::new(target);
+        target.is_relative() && target.components().next().is_some() && info.path.ends_with(target)
     }
 
-    false
-}
-
-fn matches_branch(info: &WorktreeInfo, target: &str) -> bool {
-    info.branch
-        .as_ref()
-        .map(|branch| branch == target)
-        .unwrap_or(false)
-}
+    /// Names a fuzzy target is compared against.
+    fn names(&self, info: &WorktreeInfo) -> Vec<String> {
+        let mut names: Vec<String> = info.branch.iter().cloned().collect();
+        if info.is_main {
+            names.push(self.repo_name.to_string());
+        } else {
+            names.push(common::display_name(info, self.base_dir));
+            if let Some(name) = info.path.file_name() {
+                names.push(name.to_string_lossy().into_owned());
+            }
+        }
+        names
+    }
 
-fn matches_display_name(info: &WorktreeInfo, base_dir: &Path, target: &str) -> bool {
-    if info.is_main {
-        return false;
+    fn label(&self, info: &WorktreeInfo) -> String {
+        if info.is_main {
+            return "@".to_string();
+        }
+        common::display_name(info, self.base_dir)
     }
 
-    let display_name = common::display_name(info, base_dir);
-    display_name == target
-}
+    /// The match visited most, if history clearly prefers one.
+    fn most_frecent(&self, matches: &[&'a WorktreeInfo]) -> Option<&'a WorktreeInfo> {
+        let frecency = self.frecency?;
+        let mut scored: Vec<(f64, &'a WorktreeInfo)> = matches
+            .iter()
+            .map(|info| (frecency.score(&info.path), *info))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        match scored.as_slice() {
+            [(best, info), (second, _), ..] if *best > *second => Some(info),
+            _ => None,
+        }
+    }
 
-fn matches_directory_name(info: &WorktreeInfo, target: &str) -> bool {
-    info.path
-        .file_name()
-        .map(|name| name.to_string_lossy() == target)
-        .unwrap_or(false)
-}
+    fn ambiguous(&self, target: &str, matches: &[&WorktreeInfo]) -> AppError {
+        let mut names: Vec<String> = matches.iter().map(|info| self.label(info)).collect();
+        names.sort();
+        AppError::user(format!(
+            "worktree '{}' is ambiguous; it matches: {}\nUse a longer name to pick one.",
+            target,
+            names.join(", ")
+        ))
+    }
 
-fn worktree_not_found(
-    target: &str,
-    worktrees: &[WorktreeInfo],
-    base_dir: &Path,
-    repo_name: &str,
-) -> AppError {
-    let mut available = Vec::new();
-    for info in worktrees {
-        if !common::is_managed(info, base_dir) {
-            continue;
+    /// `not_found` for an exact-only lookup, naming the worktrees a fuzzy
+    /// lookup would have considered.
+    fn not_found_near(&self, target: &str, matches: &[&WorktreeInfo]) -> AppError {
+        if matches.is_empty() {
+            return self.not_found(target);
         }
-        available.push(common::display_name(info, base_dir));
+        let mut names: Vec<String> = matches.iter().map(|info| self.label(info)).collect();
+        names.sort();
+        AppError::user(format!(
+            "worktree '{}' not found\nDid you mean: {}?",
+            target,
+            names.join(", ")
+        ))
     }
 
-    if let Some(main) = worktrees.iter().find(|info| info.is_main) {
-        available.insert(0, "@".to_string());
-        if let Some(branch) = &main.branch {
-            if !available.iter().any(|name| name == branch) {
+    pub(crate) fn not_found(&self, target: &str) -> AppError {
+        let mut available: Vec<String> = self
+            .candidates()
+            .filter(|info| !info.is_main)
+            .map(|info| common::display_name(info, self.base_dir))
+            .collect();
+
+        if self.include_main
+            && let Some(main) = self.worktrees.iter().find(|info| info.is_main)
+        {
+            available.push("@".to_string());
+            if let Some(branch) = &main.branch
+                && !available.iter().any(|name| name == branch)
+            {
                 available.push(branch.clone());
             }
+            if !available
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(self.repo_name))
+            {
+                available.push(self.repo_name.to_string());
+            }
         }
-        if !available
-            .iter()
-            .any(|name| name.eq_ignore_ascii_case(repo_name))
-        {
-            available.push(repo_name.to_string());
-        }
-    }
 
-    available.sort();
-    available.dedup();
+        available.sort();
+        available.dedup();
 
-    let suggestion = if available.is_empty() {
-        String::from("Run 'wtw list' to see available worktrees.")
-    } else {
-        format!(
-            "Available worktrees: {}\nRun 'wtw list' to see available worktrees.",
-            available.join(", ")
-        )
-    };
+        let suggestion = if available.is_empty() {
+            String::from("Run 'wtw list' to see available worktrees.")
+        } else {
+            format!(
+                "Available worktrees: {}\nRun 'wtw list' to see available worktrees.",
+                available.join(", ")
+            )
+        };
+
+        AppError::user(format!("worktree '{}' not found\n{}", target, suggestion))
+    }
+}
+
+fn matches_main_alias(repo_name: &str, target: &str) -> bool {
+    target == "@" || target.eq_ignore_ascii_case("root") || target.eq_ignore_ascii_case(repo_name)
+}
 
-    AppError::user(format!("worktree '{}' not found\n{}", target, suggestion))
+fn is_prefix(needle: &str, name: &str) -> bool {
+    name.starts_with(needle)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::git::worktree::WorktreeInfo;
     use crate::worktree::common;
+    use crate::worktree::history::Frecency;
     use std::fs;
     use std::path::{Path, PathBuf};
     use tempfile::TempDir;
 
+    #[test]
+    fn split_target_separates_worktree_and_inner_path() {
+        assert_eq!(
+            super::split_target("feature/auth:src/main.rs"),
+            ("feature/auth", Some("src/main.rs"))
+        );
+        assert_eq!(
+            super::split_target("feature/auth:"),
+            ("feature/auth", Some(""))
+        );
+        assert_eq!(super::split_target("feature/auth"), ("feature/auth", None));
+        assert_eq!(super::split_target(r"C:\repo\wt"), (r"C:\repo\wt", None));
+        assert_eq!(
+            super::split_target(r"C:\repo\wt:src"),
+            (r"C:\repo\wt", Some("src"))
+        );
+    }
+
+    #[test]
+    fn join_inner_stays_inside_the_worktree() {
+        let root = Path::new("/repo/worktree/feature");
+        assert_eq!(
+            super::join_inner(root, "src/main.rs").unwrap(),
+            root.join("src/main.rs")
+        );
+        assert!(super::join_inner(root, "").is_ok());
+        assert!(super::join_inner(root, "../other").is_err());
+        assert!(super::join_inner(root, "src/../../other").is_err());
+        assert!(super::join_inner(root, "/etc").is_err());
+    }
+
+    #[test]
+    fn same_subdir_falls_back_to_target_root() {
+        let temp = TempDir::new().expect("temp dir");
+        let current = temp.path().join("main");
+        let target = temp.path().join("feature");
+        fs::create_dir_all(target.join("backend").join("api")).unwrap();
+
+        assert_eq!(
+            super::same_subdir(&current.join("backend").join("api"), &current, &target),
+            target.join("backend").join("api")
+        );
+        assert_eq!(
+            super::same_subdir(&current.join("docs"), &current, &target),
+            target
+        );
+        assert_eq!(super::same_subdir(&current, &current, &target), target);
+        assert_eq!(
+            super::same_subdir(Path::new("/elsewhere"), &current, &target),
+            target
+        );
+    }
+
     #[test]
     fn sanitize_target_trims_whitespace_and_wildcards() {
         assert_eq!(super::sanitize_target("  feature/*  "), "feature/");
@@ -185,15 +525,26 @@ mod tests {
         let base_dir = fixture.base_dir.clone();
         let repo = fixture.repo_name.clone();
 
-        let resolved_main = super::resolve_path(&worktrees, &base_dir, &repo, "@").unwrap();
+        let resolved_main =
+            super::resolve_path(&worktrees, &base_dir, &repo, "@", &Frecency::default()).unwrap();
         assert_eq!(common::normalize_path(&resolved_main), fixture.main_path);
 
-        let resolved_repo = super::resolve_path(&worktrees, &base_dir, &repo, &repo).unwrap();
+        let resolved_repo =
+            super::resolve_path(&worktrees, &base_dir, &repo, &repo, &Frecency::default()).unwrap();
         assert_eq!(common::normalize_path(&resolved_repo), fixture.main_path);
 
-        let resolved_branch =
-            super::resolve_path(&worktrees, &base_dir, &repo, "feature/auth").unwrap();
-        assert_eq!(common::normalize_path(&resolved_branch), fixture.feature_path);
+        let resolved_branch = super::resolve_path(
+            &worktrees,
+            &base_dir,
+            &repo,
+            "feature/auth",
+            &Frecency::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            common::normalize_path(&resolved_branch),
+            fixture.feature_path
+        );
     }
 
     #[test]
@@ -202,9 +553,14 @@ mod tests {
         let worktrees = fixture.worktrees.clone();
         let base_dir = fixture.base_dir.clone();
 
-        let resolved =
-            super::resolve_path(&worktrees, &base_dir, &fixture.repo_name, &fixture.feature_display)
-                .unwrap();
+        let resolved = super::resolve_path(
+            &worktrees,
+            &base_dir,
+            &fixture.repo_name,
+            &fixture.feature_display,
+            &Frecency::default(),
+        )
+        .unwrap();
         assert_eq!(common::normalize_path(&resolved), fixture.feature_path);
     }
 
@@ -212,7 +568,7 @@ mod tests {
     fn worktree_not_found_lists_available_options() {
         let fixture = Fixture::new();
         let err =
-            super::worktree_not_found("ghost", &fixture.worktrees, &fixture.base_dir, "repo");
+            super::Resolver::new(&fixture.worktrees, &fixture.base_dir, "repo").not_found("ghost");
         let message = format!("{err}");
         assert!(
             message.contains("Available worktrees"),
@@ -222,6 +578,84 @@ mod tests {
         assert!(message.contains("@"));
     }
 
+    #[test]
+    fn resolver_falls_back_to_unique_prefix_then_subsequence() {
+        let fixture = Fixture::new();
+        let resolver =
+            super::Resolver::new(&fixture.worktrees, &fixture.base_dir, &fixture.repo_name)
+                .fuzzy(true);
+
+        let by_prefix = resolver.resolve("au").unwrap();
+        assert_eq!(
+            common::normalize_path(&by_prefix.path),
+            fixture.feature_path
+        );
+
+        let by_subsequence = resolver.resolve("fth").unwrap();
+        assert_eq!(
+            common::normalize_path(&by_subsequence.path),
+            fixture.feature_path
+        );
+
+        let by_case = resolver.resolve("BUG").unwrap();
+        assert_eq!(by_case.branch.as_deref(), Some("bugfix/one"));
+    }
+
+    #[test]
+    fn resolver_reports_ambiguous_fuzzy_matches() {
+        let fixture = Fixture::new();
+        let resolver =
+            super::Resolver::new(&fixture.worktrees, &fixture.base_dir, &fixture.repo_name)
+                .fuzzy(true);
+
+        let message = resolver.resolve("u").unwrap_err().to_string();
+        assert!(message.contains("ambiguous"), "got: {message}");
+        assert!(message.contains("bugfix"));
+        assert!(message.contains("feature"));
+    }
+
+    #[test]
+    fn resolve_match_tells_guesses_from_exact_matches() {
+        let fixture = Fixture::new();
+        let resolver =
+            super::Resolver::new(&fixture.worktrees, &fixture.base_dir, &fixture.repo_name)
+                .fuzzy(true);
+
+        let (_, guessed) = resolver.resolve_match("feature/auth").unwrap();
+        assert!(!guessed);
+        let (info, guessed) = resolver.resolve_match("au").unwrap();
+        assert!(guessed);
+        assert_eq!(common::normalize_path(&info.path), fixture.feature_path);
+    }
+
+    #[test]
+    fn resolver_is_exact_unless_fuzzy() {
+        let fixture = Fixture::new();
+        let resolver =
+            super::Resolver::new(&fixture.worktrees, &fixture.base_dir, &fixture.repo_name);
+
+        assert!(resolver.resolve("feature/auth").is_ok());
+        let message = resolver.resolve("au").unwrap_err().to_string();
+        assert!(message.contains("not found"), "got: {message}");
+        assert!(message.contains("Did you mean"), "got: {message}");
+        assert!(message.contains("auth"), "got: {message}");
+    }
+
+    #[test]
+    fn resolver_without_main_never_returns_main_worktree() {
+        let fixture = Fixture::new();
+        let resolver =
+            super::Resolver::new(&fixture.worktrees, &fixture.base_dir, &fixture.repo_name)
+                .include_main(false);
+
+        assert!(resolver.resolve("@").is_err());
+        assert!(resolver.resolve("main").is_err());
+        let err = resolver
+            .resolve(fixture.main_path.to_str().unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("main worktree"));
+    }
+
     #[derive(Debug)]
     struct Fixture {
         #[allow(dead_code)]
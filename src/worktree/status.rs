@@ -0,0 +1,696 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use serde::Serialize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::cli::StatusCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::status::{self, WorktreeStatus};
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::common;
+use crate::worktree::history::{self, History};
+
+const DAY: u64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    /// Work is stuck or at risk until someone acts.
+    Error,
+    /// Probably needs attention.
+    Warning,
+    Info,
+}
+
+/// What a finding is about, for dashboards that group or filter them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FindingKind {
+    Missing,
+    Conflicts,
+    Operation,
+    Uncommitted,
+    Unpushed,
+    UpstreamGone,
+    Stashes,
+    Untouched,
+    Behind,
+    NoUpstream,
+    Detached,
+}
+
+impl FindingKind {
+    fn severity(self) -> Severity {
+        match self {
+            FindingKind::Missing | FindingKind::Conflicts | FindingKind::Operation => {
+                Severity::Error
+            }
+            FindingKind::Uncommitted
+            | FindingKind::Unpushed
+            | FindingKind::UpstreamGone
+            | FindingKind::Stashes
+            | FindingKind::Untouched => Severity::Warning,
+            FindingKind::Behind | FindingKind::NoUpstream | FindingKind::Detached => Severity::Info,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Finding {
+    worktree: String,
+    kind: FindingKind,
+    message: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Findings {
+    error: Vec<Finding>,
+    warning: Vec<Finding>,
+    info: Vec<Finding>,
+}
+
+impl Findings {
+    fn push(&mut self, finding: Finding) {
+        match finding.kind.severity() {
+            Severity::Error => self.error.push(finding),
+            Severity::Warning => self.warning.push(finding),
+            Severity::Info => self.info.push(finding),
+        }
+    }
+
+    fn groups(&self) -> [(&'static str, &[Finding]); 3] {
+        [
+            ("Errors", &self.error),
+            ("Warnings", &self.warning),
+            ("Info", &self.info),
+        ]
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct WorktreeSummary {
+    name: String,
+    branch: Option<String>,
+    path: String,
+    /// Bytes under the worktree, excluding `.git` and nested worktrees;
+    /// `None` with `--no-disk` or when the directory is missing.
+    disk_bytes: Option<u64>,
+    /// Unix seconds of the latest commit, index update or `wtw cd` visit.
+    last_touched: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    worktrees: Vec<WorktreeSummary>,
+    findings: Findings,
+    disk_bytes: u64,
+}
+
+/// The upstream state of a local branch, from `git for-each-ref`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Tracking {
+    upstream: Option<String>,
+    ahead: usize,
+    behind: usize,
+    gone: bool,
+}
+
+/// Everything read from one worktree, gathered in parallel.
+struct Probe {
+    missing: bool,
+    status: Option<WorktreeStatus>,
+    commit_time: Option<u64>,
+    index_time: Option<u64>,
+    disk_bytes: Option<u64>,
+}
+
+pub fn run(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &StatusCommand,
+) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
+    let names = common::display_names(&worktrees, &base_dir);
+    let tracking = read_tracking(git)?;
+    let stashes = count_stashes(git)?;
+    let has_remotes = !git
+        .run(["remote"])
+        .map_err(|err| AppError::git(err.to_string()))?
+        .stdout()
+        .trim()
+        .is_empty();
+    let now = history::now();
+    let frecency = History::load(repo).frecency(now);
+
+    let roots: Vec<PathBuf> = worktrees
+        .iter()
+        .map(|info| common::normalize_path(&info.path))
+        .collect();
+    let probes = common::parallel_map(&worktrees, |info| -> Result<Probe> {
+        let path = common::normalize_path(&info.path);
+        if !path.exists() {
+            return Ok(Probe {
+                missing: true,
+                status: None,
+                commit_time: commit_time(git, &info.head),
+                index_time: None,
+                disk_bytes: None,
+            });
+        }
+        // `git status` may refresh the index, so its mtime is read first.
+        let index_time = index_time(&path);
+        Ok(Probe {
+            missing: false,
+            status: Some(status::read_status(git, &path)?),
+            commit_time: commit_time(git, &info.head),
+            index_time,
+            disk_bytes: (!cmd.no_disk).then(|| {
+                let nested: Vec<&Path> = roots
+                    .iter()
+                    .filter(|root| **root != path && root.starts_with(&path))
+                    .map(PathBuf::as_path)
+                    .collect();
+                disk_usage(&path, &nested)
+            }),
+        })
+    });
+
+    let mut report = Report {
+        worktrees: Vec::with_capacity(worktrees.len()),
+        findings: Findings::default(),
+        disk_bytes: 0,
+    };
+    for ((info, name), probe) in worktrees.iter().zip(names).zip(probes) {
+        let probe = probe?;
+        let last_visit = frecency.rank(&info.path).map(|rank| rank.last_visit);
+        let last_touched = [probe.commit_time, probe.index_time, last_visit]
+            .into_iter()
+            .flatten()
+            .max();
+        let branch_tracking = info
+            .branch
+            .as_ref()
+            .map(|branch| tracking.get(branch).cloned().unwrap_or_default());
+        let stash_count = info
+            .branch
+            .as_ref()
+            .and_then(|branch| stashes.get(branch).copied())
+            .unwrap_or(0);
+
+        let check = Check {
+            info,
+            missing: probe.missing,
+            status: probe.status.as_ref(),
+            tracking: branch_tracking.as_ref(),
+            stashes: stash_count,
+            has_remotes,
+            untouched_days: last_touched.map(|time| now.saturating_sub(time) / DAY),
+            stale_days: cmd.stale_days,
+        };
+        for (kind, message) in check.findings() {
+            report.findings.push(Finding {
+                worktree: name.clone(),
+                kind,
+                message,
+            });
+        }
+
+        report.disk_bytes += probe.disk_bytes.unwrap_or(0);
+        report.worktrees.push(WorktreeSummary {
+            name,
+            branch: info.branch.clone(),
+            path: common::normalize_path(&info.path)
+                .to_string_lossy()
+                .into_owned(),
+            disk_bytes: probe.disk_bytes,
+            last_touched,
+        });
+    }
+
+    let mut stdout = io::stdout().lock();
+    if cmd.json {
+        serde_json::to_writer_pretty(&mut stdout, &report)?;
+        writeln!(stdout)?;
+        return Ok(());
+    }
+    output_report(&mut stdout, &report, now)
+}
+
+/// The inputs for one worktree's findings.
+struct Check<'a> {
+    info: &'a WorktreeInfo,
+    missing: bool,
+    status: Option<&'a WorktreeStatus>,
+    tracking: Option<&'a Tracking>,
+    stashes: usize,
+    /// Without remotes, a missing upstream is not worth mentioning.
+    has_remotes: bool,
+    untouched_days: Option<u64>,
+    stale_days: u64,
+}
+
+impl Check<'_> {
+    fn findings(&self) -> Vec<(FindingKind, String)> {
+        let mut findings = Vec::new();
+
+        if self.missing {
+            let message = match self.info.prunable.as_deref() {
+                Some(reason) if !reason.is_empty() => {
+                    format!("directory is missing ({}); run 'wtw prune'", reason)
+                }
+                _ => "directory is missing; run 'wtw prune'".to_string(),
+            };
+            findings.push((FindingKind::Missing, message));
+        }
+
+        if let Some(status) = self.status {
+            if let Some(operation) = status.operation {
+                findings.push((
+                    FindingKind::Operation,
+                    format!("{} in progress", operation.name()),
+                ));
+            }
+            let changes = status.changes;
+            if changes.conflicted > 0 {
+                findings.push((
+                    FindingKind::Conflicts,
+                    format!(
+                        "{} {}",
+                        changes.conflicted,
+                        common::plural(changes.conflicted, "conflicted file", "conflicted files")
+                    ),
+                ));
+            }
+            let parts: Vec<String> = [
+                (changes.staged, "staged"),
+                (changes.modified, "modified"),
+                (changes.untracked, "untracked"),
+            ]
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, label)| format!("{} {}", count, label))
+            .collect();
+            if !parts.is_empty() {
+                findings.push((
+                    FindingKind::Uncommitted,
+                    format!("uncommitted changes ({})", parts.join(", ")),
+                ));
+            }
+        }
+
+        // Stashes are matched by the branch name in their subject, so the
+        // message names the branch rather than claiming the worktree.
+        if let Some(branch) = self.info.branch.as_deref()
+            && self.stashes > 0
+        {
+            findings.push((
+                FindingKind::Stashes,
+                format!(
+                    "{} {} made on a branch named '{}'",
+                    self.stashes,
+                    common::plural(self.stashes, "stash", "stashes"),
+                    branch
+                ),
+            ));
+        }
+
+        match self.tracking {
+            None => findings.push((FindingKind::Detached, "HEAD is detached".to_string())),
+            Some(tracking) => match &tracking.upstream {
+                None if self.has_remotes => {
+                    findings.push((FindingKind::NoUpstream, "no upstream branch".to_string()))
+                }
+                None => {}
+                Some(upstream) if tracking.gone => findings.push((
+                    FindingKind::UpstreamGone,
+                    format!("upstream '{}' no longer exists", upstream),
+                )),
+                Some(upstream) => {
+                    if tracking.ahead > 0 {
+                        findings.push((
+                            FindingKind::Unpushed,
+                            format!(
+                                "{} {} not pushed to '{}'",
+                                tracking.ahead,
+                                common::plural(tracking.ahead, "commit", "commits"),
+                                upstream
+                            ),
+                        ));
+                    }
+                    if tracking.behind > 0 {
+                        findings.push((
+                            FindingKind::Behind,
+                            format!(
+                                "{} {} behind '{}'",
+                                tracking.behind,
+                                common::plural(tracking.behind, "commit", "commits"),
+                                upstream
+                            ),
+                        ));
+                    }
+                }
+            },
+        }
+
+        // Locked worktrees are kept on purpose, however old they are.
+        if let Some(days) = self.untouched_days
+            && self.stale_days > 0
+            && days >= self.stale_days
+            && self.info.locked.is_none()
+        {
+            findings.push((
+                FindingKind::Untouched,
+                format!("not touched for {} days", days),
+            ));
+        }
+
+        findings
+    }
+}
+
+/// Upstream, ahead/behind and gone state of every local branch.
+fn read_tracking(git: &GitRunner) -> Result<HashMap<String, Tracking>> {
+    let output = git
+        .run([
+            "for-each-ref",
+            "--format=%(refname:short)%09%(upstream:short)%09%(upstream:track,nobracket)",
+            "refs/heads",
+        ])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    Ok(output.stdout().lines().filter_map(parse_tracking).collect())
+}
+
+fn parse_tracking(line: &str) -> Option<(String, Tracking)> {
+    let mut fields = line.split('\t');
+    let branch = fields.next()?.to_string();
+    let upstream = fields.next().filter(|upstream| !upstream.is_empty());
+    let track = fields.next().unwrap_or_default();
+
+    let mut tracking = Tracking {
+        upstream: upstream.map(str::to_string),
+        gone: track == "gone",
+        ..Tracking::default()
+    };
+    for part in track.split(", ") {
+        if let Some(count) = part.strip_prefix("ahead ") {
+            tracking.ahead = count.parse().unwrap_or(0);
+        } else if let Some(count) = part.strip_prefix("behind ") {
+            tracking.behind = count.parse().unwrap_or(0);
+        }
+    }
+    Some((branch, tracking))
+}
+
+/// Stash entries per branch. Stashes belong to the repository; each is
+/// attributed by the branch name in its subject, so a stash made on an
+/// earlier branch of the same name (or one renamed since) is counted for
+/// whichever branch has that name now.
+fn count_stashes(git: &GitRunner) -> Result<HashMap<String, usize>> {
+    let output = git
+        .run(["stash", "list", "--format=%gs"])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    let mut counts = HashMap::new();
+    for branch in output.stdout().lines().filter_map(stash_branch) {
+        *counts.entry(branch.to_string()).or_insert(0) += 1;
+    }
+    Ok(counts)
+}
+
+/// The branch of a stash subject: `WIP on <branch>: …` or `On <branch>: …`.
+fn stash_branch(subject: &str) -> Option<&str> {
+    let rest = subject
+        .strip_prefix("WIP on ")
+        .or_else(|| subject.strip_prefix("On "))?;
+    let (branch, _) = rest.split_once(':')?;
+    (branch != "(no branch)").then_some(branch)
+}
+
+fn commit_time(git: &GitRunner, head: &str) -> Option<u64> {
+    if head.is_empty() || head.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    git.run(["show", "-s", "--format=%ct", head])
+        .ok()
+        .and_then(|output| output.stdout().trim().parse().ok())
+}
+
+fn index_time(worktree_path: &Path) -> Option<u64> {
+    status::git_dir(worktree_path)
+        .and_then(|dir| fs::metadata(dir.join("index")).ok())
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+}
+
+/// Total size of the files under `root`, without following symlinks and
+/// skipping `.git` and the `nested` worktrees. Unreadable entries count as
+/// empty.
+fn disk_usage(root: &Path, nested: &[&Path]) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_name() == ".git" || nested.contains(&path.as_path()) {
+                continue;
+            }
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => pending.push(path),
+                Ok(_) => total += entry.metadata().map_or(0, |metadata| metadata.len()),
+                Err(_) => {}
+            }
+        }
+    }
+    total
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+fn output_report<W: Write>(writer: &mut W, report: &Report, now: u64) -> Result<()> {
+    let disk: Vec<String> = report
+        .worktrees
+        .iter()
+        .map(|worktree| worktree.disk_bytes.map_or("-".to_string(), format_bytes))
+        .collect();
+    let touched: Vec<String> = report
+        .worktrees
+        .iter()
+        .map(|worktree| {
+            worktree.last_touched.map_or("-".to_string(), |time| {
+                history::describe_age(now.saturating_sub(time))
+            })
+        })
+        .collect();
+    let branches: Vec<&str> = report
+        .worktrees
+        .iter()
+        .map(|worktree| worktree.branch.as_deref().unwrap_or("detached"))
+        .collect();
+
+    let mut name_width = "PATH".len();
+    let mut branch_width = "BRANCH".len();
+    let mut disk_width = "DISK".len();
+    for (idx, worktree) in report.worktrees.iter().enumerate() {
+        name_width = name_width.max(worktree.name.width());
+        branch_width = branch_width.max(branches[idx].width());
+        disk_width = disk_width.max(disk[idx].len());
+    }
+
+    writeln!(
+        writer,
+        "{} {} {:>disk_width$} TOUCHED",
+        common::pad("PATH", name_width),
+        common::pad("BRANCH", branch_width),
+        "DISK"
+    )?;
+    writeln!(
+        writer,
+        "{:-<name_width$} {:-<branch_width$} {:-<disk_width$} -------",
+        "", "", ""
+    )?;
+    for (idx, worktree) in report.worktrees.iter().enumerate() {
+        writeln!(
+            writer,
+            "{} {} {:>disk_width$} {}",
+            common::pad(&worktree.name, name_width),
+            common::pad(branches[idx], branch_width),
+            disk[idx],
+            touched[idx]
+        )?;
+    }
+
+    let findings = &report.findings;
+    if findings.error.is_empty() && findings.warning.is_empty() && findings.info.is_empty() {
+        writeln!(
+            writer,
+            "\nNo problems found in {} worktree{}",
+            report.worktrees.len(),
+            common::plural(report.worktrees.len(), "", "s")
+        )?;
+        return Ok(());
+    }
+
+    let worktree_width = findings
+        .groups()
+        .iter()
+        .flat_map(|(_, group)| group.iter())
+        .map(|finding| finding.worktree.width())
+        .max()
+        .unwrap_or(0);
+    for (title, group) in findings.groups() {
+        if group.is_empty() {
+            continue;
+        }
+        writeln!(writer, "\n{} ({})", title, group.len())?;
+        for finding in group {
+            writeln!(
+                writer,
+                "  {} {}",
+                common::pad(&finding.worktree, worktree_width),
+                finding.message
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::status::{ChangeCounts, Operation};
+
+    fn info(branch: Option<&str>) -> WorktreeInfo {
+        WorktreeInfo {
+            path: PathBuf::from("/src/worktree/feature"),
+            head: "0123456789abcdef".to_string(),
+            branch: branch.map(str::to_string),
+            is_main: false,
+            is_detached: branch.is_none(),
+            locked: None,
+            prunable: None,
+        }
+    }
+
+    fn kinds(check: &Check<'_>) -> Vec<FindingKind> {
+        check.findings().into_iter().map(|(kind, _)| kind).collect()
+    }
+
+    #[test]
+    fn parses_tracking_and_stash_subjects() {
+        assert_eq!(
+            parse_tracking("feature/x\torigin/feature/x\tahead 2, behind 1"),
+            Some((
+                "feature/x".to_string(),
+                Tracking {
+                    upstream: Some("origin/feature/x".to_string()),
+                    ahead: 2,
+                    behind: 1,
+                    gone: false,
+                }
+            ))
+        );
+        let (_, gone) = parse_tracking("old\torigin/old\tgone").unwrap();
+        assert!(gone.gone);
+        let (_, local) = parse_tracking("local\t\t").unwrap();
+        assert_eq!(local, Tracking::default());
+
+        assert_eq!(
+            stash_branch("WIP on feature/x: 0123abc msg"),
+            Some("feature/x")
+        );
+        assert_eq!(stash_branch("On main: saved"), Some("main"));
+        assert_eq!(stash_branch("WIP on (no branch): 0123abc msg"), None);
+    }
+
+    #[test]
+    fn classifies_findings_by_severity() {
+        let info = info(Some("feature"));
+        let status = WorktreeStatus {
+            changes: ChangeCounts {
+                staged: 0,
+                modified: 2,
+                untracked: 1,
+                conflicted: 1,
+            },
+            operation: Some(Operation::Rebase),
+        };
+        let tracking = Tracking {
+            upstream: Some("origin/feature".to_string()),
+            ahead: 3,
+            behind: 0,
+            gone: false,
+        };
+        let check = Check {
+            info: &info,
+            missing: false,
+            status: Some(&status),
+            tracking: Some(&tracking),
+            stashes: 1,
+            has_remotes: true,
+            untouched_days: Some(45),
+            stale_days: 30,
+        };
+        let findings = check.findings();
+        assert_eq!(
+            kinds(&check),
+            [
+                FindingKind::Operation,
+                FindingKind::Conflicts,
+                FindingKind::Uncommitted,
+                FindingKind::Stashes,
+                FindingKind::Unpushed,
+                FindingKind::Untouched,
+            ]
+        );
+        assert_eq!(findings[0].1, "rebase in progress");
+        assert_eq!(
+            findings[2].1,
+            "uncommitted changes (2 modified, 1 untracked)"
+        );
+        assert_eq!(findings[3].1, "1 stash made on a branch named 'feature'");
+        assert_eq!(findings[4].1, "3 commits not pushed to 'origin/feature'");
+        assert_eq!(FindingKind::Operation.severity(), Severity::Error);
+        assert_eq!(FindingKind::Untouched.severity(), Severity::Warning);
+
+        let check = Check {
+            stale_days: 0,
+            tracking: None,
+            status: None,
+            stashes: 0,
+            ..check
+        };
+        assert_eq!(kinds(&check), [FindingKind::Detached]);
+    }
+
+    #[test]
+    fn formats_sizes_in_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use tracing::warn;
+
+use crate::git::rev::RepoContext;
+use crate::git::status::{self, ChangeCounts, Operation, WorktreeStatus};
+use crate::worktree::common;
+
+/// A cached `git status` result and the state it was computed for.
+#[derive(Debug, Clone, PartialEq)]
+struct CacheEntry {
+    head: String,
+    index_mtime: u128,
+    status: WorktreeStatus,
+}
+
+/// Per-worktree `git status` results for `wtw list --cached`.
+///
+/// An entry is reused while the worktree's HEAD commit and the mtime of its
+/// index are unchanged, so edits to tracked files that Git has not noticed
+/// yet are only picked up by the next uncached `wtw list`.
+#[derive(Debug, Default)]
+pub(crate) struct StatusCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+    dirty: bool,
+}
+
+impl StatusCache {
+    /// Read the cache; a missing or unreadable file is an empty cache.
+    pub(crate) fn load(repo: &RepoContext) -> Self {
+        let contents = fs::read_to_string(cache_path(repo)).unwrap_or_default();
+        Self::parse(&contents)
+    }
+
+    fn parse(contents: &str) -> Self {
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                let [
+                    path,
+                    head,
+                    mtime,
+                    staged,
+                    modified,
+                    untracked,
+                    conflicted,
+                    operation,
+                ] = fields[..]
+                else {
+                    return None;
+                };
+                let entry = CacheEntry {
+                    head: head.to_string(),
+                    index_mtime: mtime.parse().ok()?,
+                    status: WorktreeStatus {
+                        changes: ChangeCounts {
+                            staged: staged.parse().ok()?,
+                            modified: modified.parse().ok()?,
+                            untracked: untracked.parse().ok()?,
+                            conflicted: conflicted.parse().ok()?,
+                        },
+                        operation: parse_operation(operation)?,
+                    },
+                };
+                Some((PathBuf::from(path), entry))
+            })
+            .collect();
+        Self {
+            entries,
+            dirty: false,
+        }
+    }
+
+    fn serialize(&self) -> String {
+        let mut paths: Vec<&PathBuf> = self.entries.keys().collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let entry = &self.entries[path];
+                let changes = entry.status.changes;
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                    path.display(),
+                    entry.head,
+                    entry.index_mtime,
+                    changes.staged,
+                    changes.modified,
+                    changes.untracked,
+                    changes.conflicted,
+                    entry.status.operation.map_or("-", Operation::name),
+                )
+            })
+            .collect()
+    }
+
+    /// The cached status of the worktree at `path`, if it is still valid for
+    /// `head` and the current index.
+    pub(crate) fn get(&self, path: &Path, head: &str) -> Option<WorktreeStatus> {
+        let entry = self.entries.get(&common::normalize_path(path))?;
+        (entry.head == head && entry.index_mtime == index_mtime(path)).then(|| entry.status.clone())
+    }
+
+    /// Remember a freshly read status. Call this after running `git status`,
+    /// which may itself rewrite the index.
+    pub(crate) fn insert(&mut self, path: &Path, head: &str, status: WorktreeStatus) {
+        let entry = CacheEntry {
+            head: head.to_string(),
+            index_mtime: index_mtime(path),
+            status,
+        };
+        let previous = self
+            .entries
+            .insert(common::normalize_path(path), entry.clone());
+        self.dirty |= previous.as_ref() != Some(&entry);
+    }
+
+    /// Drop entries for worktrees that no longer exist.
+    pub(crate) fn retain(&mut self, worktrees: &[PathBuf]) {
+        let before = self.entries.len();
+        self.entries
+            .retain(|path, _| worktrees.iter().any(|worktree| worktree == path));
+        self.dirty |= self.entries.len() != before;
+    }
+
+    /// Write the cache back if anything changed.
+    pub(crate) fn save(&self, repo: &RepoContext) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let path = cache_path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Completions can run `wtw list --cached` concurrently.
+        common::write_atomic(&path, &self.serialize())
+    }
+}
+
+/// Forget every cached status after worktrees were added, moved or removed.
+///
+/// Failing to delete the cache is only logged: the entries are still
+/// validated against HEAD and the index on use.
+pub(crate) fn invalidate(repo: &RepoContext) {
+    match fs::remove_file(cache_path(repo)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            warn!("failed to invalidate the status cache: {}", err);
+        }
+        _ => {}
+    }
+}
+
+/// Nanoseconds since the epoch of the worktree's index, or 0 when it has
+/// none (yet).
+fn index_mtime(worktree_path: &Path) -> u128 {
+    status::git_dir(worktree_path)
+        .and_then(|dir| fs::metadata(dir.join("index")).ok())
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos())
+}
+
+/// `Some(None)` for `-` (no operation), `None` for an unknown name.
+fn parse_operation(name: &str) -> Option<Option<Operation>> {
+    let operation = match name {
+        "-" => return Some(None),
+        "merge" => Operation::Merge,
+        "rebase" => Operation::Rebase,
+        "cherry-pick" => Operation::CherryPick,
+        "revert" => Operation::Revert,
+        "bisect" => Operation::Bisect,
+        _ => return None,
+    };
+    Some(Some(operation))
+}
+
+fn cache_path(repo: &RepoContext) -> PathBuf {
+    repo.common_dir().join("wtw").join("status-cache")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_status() -> WorktreeStatus {
+        WorktreeStatus {
+            changes: ChangeCounts {
+                staged: 1,
+                modified: 2,
+                untracked: 0,
+                conflicted: 3,
+            },
+            operation: Some(Operation::CherryPick),
+        }
+    }
+
+    #[test]
+    fn cache_round_trips_and_skips_malformed_lines() {
+        let mut cache = StatusCache::default();
+        cache.insert(Path::new("/wt/a"), "abc", sample_status());
+        cache.insert(Path::new("/wt/b"), "def", WorktreeStatus::default());
+        assert!(cache.dirty);
+
+        let text = format!(
+            "garbage\n{}/wt/c\tx\t1\t0\t0\t0\t0\tunknown\n",
+            cache.serialize()
+        );
+        let parsed = StatusCache::parse(&text);
+        assert_eq!(parsed.entries, cache.entries);
+        assert!(!parsed.dirty);
+    }
+
+    #[test]
+    fn entries_are_keyed_on_head_and_index_mtime() {
+        let temp = TempDir::new().unwrap();
+        let worktree = common::normalize_path(temp.path());
+        fs::create_dir_all(worktree.join(".git")).unwrap();
+        fs::write(worktree.join(".git").join("index"), "v1").unwrap();
+
+        let mut cache = StatusCache::default();
+        cache.insert(&worktree, "abc", sample_status());
+        assert_eq!(cache.get(&worktree, "abc"), Some(sample_status()));
+        assert_eq!(cache.get(&worktree, "def"), None);
+
+        let entry = cache.entries.get_mut(&worktree).unwrap();
+        entry.index_mtime -= 1;
+        assert_eq!(cache.get(&worktree, "abc"), None);
+
+        cache.retain(&[]);
+        assert!(cache.entries.is_empty());
+    }
+}
@@ -57,3 +57,103 @@ fn cd_unknown_worktree_suggests_available_options() {
         .stderr(predicate::str::contains("Run 'wtw list'"));
 }
 
+
+#[test]
+fn cd_falls_back_to_unique_prefix_and_reports_ambiguity() {
+    let repo = TestRepo::new();
+    for branch in ["feature/auth-refactor", "feature/billing", "feature/bugfix"] {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+
+    let output = repo
+        .command()
+        .args(["cd", "auth"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let cd_path = normalize_path(std::path::Path::new(
+        String::from_utf8(output).unwrap().trim(),
+    ));
+    assert_eq!(
+        cd_path,
+        normalize_path(&repo.worktree_path_for("feature/auth-refactor"))
+    );
+
+    repo.command()
+        .args(["cd", "b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("worktree 'b' is ambiguous"))
+        .stderr(predicate::str::contains("billing"))
+        .stderr(predicate::str::contains("bugfix"));
+}
+
+#[test]
+fn cd_without_target_requires_a_name_when_not_interactive() {
+    let repo = TestRepo::new();
+
+    repo.command()
+        .arg("cd")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("worktree name is required"));
+}
+
+#[test]
+fn cd_keeps_subdirectory_when_requested() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/sub");
+    repo.command()
+        .args(["add", "feature/sub"])
+        .assert()
+        .success();
+    let feature = repo.worktree_path_for("feature/sub");
+    let nested = repo.path().join("backend").join("api");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::create_dir_all(feature.join("backend").join("api")).unwrap();
+    std::fs::create_dir_all(repo.path().join("docs")).unwrap();
+
+    let cd_from = |dir: &std::path::Path, args: &[&str]| {
+        let output = repo
+            .command_in(dir)
+            .arg("cd")
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        normalize_path(std::path::Path::new(
+            String::from_utf8(output).unwrap().trim(),
+        ))
+    };
+
+    assert_eq!(cd_from(&nested, &["feature/sub"]), normalize_path(&feature));
+    assert_eq!(
+        cd_from(&nested, &["--keep-subdir", "feature/sub"]),
+        normalize_path(&feature.join("backend").join("api"))
+    );
+    assert_eq!(
+        cd_from(&repo.path().join("docs"), &["-s", "feature/sub"]),
+        normalize_path(&feature)
+    );
+
+    repo.write_config(
+        r#"version: "1.0"
+defaults:
+  base_dir: worktree
+  cd_keep_subdir: true
+"#,
+    );
+    assert_eq!(
+        cd_from(&nested, &["feature/sub"]),
+        normalize_path(&feature.join("backend").join("api"))
+    );
+    assert_eq!(
+        cd_from(&nested, &["--no-keep-subdir", "feature/sub"]),
+        normalize_path(&feature)
+    );
+}
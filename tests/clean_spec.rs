@@ -0,0 +1,234 @@
+mod common;
+
+use common::{TestRepo, branch_exists};
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[test]
+fn clean_merged_removes_clean_merged_worktrees_and_branches() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/done");
+    repo.command()
+        .args(["add", "feature/done"])
+        .assert()
+        .success();
+    commit_file(&repo.worktree_path_for("feature/done"), "done.txt");
+    merge_into_main(&repo, "feature/done");
+    repo.create_branch("feature/fresh");
+    repo.command()
+        .args(["add", "feature/fresh"])
+        .assert()
+        .success();
+    repo.create_branch("feature/wip");
+    repo.command()
+        .args(["add", "feature/wip"])
+        .assert()
+        .success();
+    let wip_path = repo.worktree_path_for("feature/wip");
+    commit_file(&wip_path, "wip.txt");
+
+    repo.command()
+        .args(["clean", "--merged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("merged"))
+        .stdout(predicate::str::contains("Removed branch 'feature/done'"));
+
+    assert!(!repo.worktree_path_for("feature/done").exists());
+    assert!(!branch_exists(repo.path(), "feature/done"));
+    assert!(wip_path.exists(), "unmerged worktree must be kept");
+    assert!(branch_exists(repo.path(), "feature/wip"));
+    assert!(
+        repo.worktree_path_for("feature/fresh").exists(),
+        "a branch without commits of its own is not merged"
+    );
+    assert!(branch_exists(repo.path(), "feature/fresh"));
+}
+
+#[test]
+fn clean_merged_keeps_fresh_branches_after_the_base_advances() {
+    let repo = TestRepo::new();
+    // Keep the main worktree clean despite the worktree directory inside it.
+    fs::write(repo.path().join(".git/info/exclude"), "worktree/\n").unwrap();
+    repo.create_branch("feature/fresh");
+    repo.command()
+        .args(["add", "feature/fresh"])
+        .assert()
+        .success();
+    // main moves on, so feature/fresh is now an ancestor of it.
+    commit_file(repo.path(), "main.txt");
+
+    repo.command()
+        .args(["clean", "--merged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No worktrees with branches merged into 'main'",
+        ));
+    assert!(repo.worktree_path_for("feature/fresh").exists());
+    assert!(branch_exists(repo.path(), "feature/fresh"));
+}
+
+#[test]
+fn clean_merged_skips_dirty_worktrees_unless_forced() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/dirty");
+    repo.command()
+        .args(["add", "feature/dirty"])
+        .assert()
+        .success();
+    let dirty_path = repo.worktree_path_for("feature/dirty");
+    commit_file(&dirty_path, "dirty.txt");
+    merge_into_main(&repo, "feature/dirty");
+    fs::write(dirty_path.join("scratch.txt"), "scratch").unwrap();
+
+    repo.command()
+        .args(["clean", "--merged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skip (dirty)"))
+        .stdout(predicate::str::contains("Nothing to remove"));
+    assert!(dirty_path.exists());
+
+    repo.command()
+        .args(["clean", "--merged", "--yes", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree"));
+    assert!(!dirty_path.exists());
+}
+
+#[test]
+fn clean_merged_dry_run_only_lists_candidates() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/listed");
+    repo.command()
+        .args(["add", "feature/listed"])
+        .assert()
+        .success();
+    commit_file(&repo.worktree_path_for("feature/listed"), "listed.txt");
+    merge_into_main(&repo, "feature/listed");
+
+    repo.command()
+        .args(["clean", "--merged", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/listed"))
+        .stdout(predicate::str::contains("remove"));
+    assert!(repo.worktree_path_for("feature/listed").exists());
+    assert!(branch_exists(repo.path(), "feature/listed"));
+
+    repo.command()
+        .args(["clean", "--merged"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "refusing to remove 1 worktree without confirmation",
+        ))
+        .stderr(predicate::str::contains("Re-run with --yes"));
+    assert!(
+        repo.worktree_path_for("feature/listed").exists(),
+        "non-interactive clean must not remove without --yes"
+    );
+    assert!(branch_exists(repo.path(), "feature/listed"));
+}
+
+#[test]
+fn clean_merged_table_aligns_wide_characters() {
+    let repo = TestRepo::new();
+    repo.create_branch("機能/認証");
+    repo.command().args(["add", "機能/認証"]).assert().success();
+    commit_file(&repo.worktree_path_for("機能/認証"), "auth.txt");
+    merge_into_main(&repo, "機能/認証");
+
+    let output = repo
+        .command()
+        .args(["clean", "--merged", "--dry-run"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    // "機能/認証" takes nine cells, so PATH and BRANCH are nine cells wide.
+    let stdout = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    let header = lines
+        .iter()
+        .position(|line| line.starts_with("PATH"))
+        .expect("table header");
+    assert!(
+        lines[header].starts_with("PATH      BRANCH    REASON"),
+        "{stdout}"
+    );
+    assert!(
+        lines[header + 2].starts_with("機能/認証 機能/認証 merged"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn clean_merged_keeps_unmerged_upstream_gone_branches_unless_forced() {
+    let repo = TestRepo::new();
+    let remote = TempDir::new().unwrap();
+    common::run_git(remote.path(), &["init", "-q", "--bare"]);
+    repo.git(&["remote", "add", "origin", remote.path().to_str().unwrap()]);
+    repo.create_branch("feature/gone");
+    repo.command()
+        .args(["add", "feature/gone"])
+        .assert()
+        .success();
+    let gone_path = repo.worktree_path_for("feature/gone");
+    commit_file(&gone_path, "gone.txt");
+    common::run_git(&gone_path, &["push", "-q", "-u", "origin", "feature/gone"]);
+    repo.git(&["push", "-q", "origin", "--delete", "feature/gone"]);
+    repo.git(&["fetch", "-q", "--prune", "origin"]);
+
+    repo.command()
+        .args(["clean", "--merged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("upstream gone"))
+        .stdout(predicate::str::contains("skip (unmerged)"))
+        .stdout(predicate::str::contains("Nothing to remove"));
+    assert!(gone_path.exists());
+    assert!(branch_exists(repo.path(), "feature/gone"));
+
+    repo.command()
+        .args(["clean", "--merged", "--yes", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed branch 'feature/gone'"));
+    assert!(!gone_path.exists());
+    assert!(!branch_exists(repo.path(), "feature/gone"));
+}
+
+#[test]
+fn clean_rejects_unknown_base_branch() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["clean", "--merged", "--base", "does-not-exist"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "base branch 'does-not-exist' not found",
+        ));
+}
+
+fn commit_file(worktree: &Path, name: &str) {
+    fs::write(worktree.join(name), name).unwrap();
+    common::run_git(worktree, &["add", name]);
+    common::run_git(worktree, &["commit", "-q", "-m", name]);
+}
+
+fn merge_into_main(repo: &TestRepo, branch: &str) {
+    repo.git(&[
+        "merge",
+        "-q",
+        "--no-ff",
+        "-m",
+        &format!("Merge {branch}"),
+        branch,
+    ]);
+}
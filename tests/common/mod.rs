@@ -17,7 +17,7 @@ impl TestRepo {
     /// Create a new repository with an initial commit and `.wtp.yml`.
     pub fn new() -> Self {
         let temp = TempDir::new().expect("create temp dir");
-        run_git(temp.path(), &["init", "-q"]);
+        run_git(temp.path(), &["init", "-q", "-b", "main"]);
         run_git(temp.path(), &["config", "user.name", "wtw-test"]);
         run_git(temp.path(), &["config", "user.email", "wtw@example.com"]);
 
@@ -104,3 +104,18 @@ pub fn run_git(dir: &Path, args: &[&str]) {
         status.code()
     );
 }
+
+#[allow(dead_code)]
+pub fn branch_exists(repo_path: &Path, branch: &str) -> bool {
+    StdCommand::new("git")
+        .current_dir(repo_path)
+        .args([
+            "show-ref",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ])
+        .status()
+        .expect("git show-ref")
+        .success()
+}
@@ -1,6 +1,7 @@
 mod common;
 
 use common::{TestRepo, normalize_path};
+use predicates::prelude::*;
 use serde::Deserialize;
 use std::path::Path;
 
@@ -132,3 +133,823 @@ fn list_json_reflects_current_and_main_flags() {
     assert!(feature.is_current);
 }
 
+#[test]
+fn list_long_reports_divergence_and_last_commit() {
+    let repo = TestRepo::new();
+    let branch = "feature/long";
+    repo.create_branch(branch);
+    repo.command().args(["add", branch]).assert().success();
+    let worktree_path = repo.worktree_path_for(branch);
+    std::fs::write(worktree_path.join("long.txt"), "long").unwrap();
+    common::run_git(&worktree_path, &["add", "long.txt"]);
+    common::run_git(&worktree_path, &["commit", "-m", "Add long listing"]);
+    common::run_git(&worktree_path, &["branch", "--set-upstream-to=main"]);
+
+    let plain: serde_json::Value = serde_json::from_slice(
+        &repo
+            .command()
+            .args(["list", "--json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout,
+    )
+    .unwrap();
+    assert!(plain[0].get("last_commit").is_none());
+
+    // Version 1 is frozen, so --long switches JSON output to version 2.
+    repo.command()
+        .args(["list", "--long", "--json", "--json-version", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot use --json-version 1"));
+    let long: serde_json::Value = serde_json::from_slice(
+        &repo
+            .command()
+            .args(["list", "--long", "--json"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout,
+    )
+    .unwrap();
+    assert!(long[0]["head_short"].is_string(), "expected version 2");
+    let entry = long
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["branch"] == branch)
+        .unwrap();
+    assert_eq!(entry["ahead"], 1);
+    assert_eq!(entry["behind"], 0);
+    assert_eq!(entry["base_branch"], "main");
+    assert_eq!(entry["base_ahead"], 1);
+    assert_eq!(entry["last_commit"]["subject"], "Add long listing");
+
+    let table = String::from_utf8(
+        repo.command()
+            .args(["list", "-l"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone(),
+    )
+    .unwrap();
+    assert!(table.contains("BASE_DIFF") && table.contains("SUBJECT"));
+    assert!(table.contains("+1/-0"));
+    assert!(table.contains("Add long listing"));
+}
+
+#[test]
+fn list_reports_change_counts_and_operation_in_progress() {
+    let repo = TestRepo::new();
+    let branch = "feature/status";
+    repo.create_branch(branch);
+    repo.command().args(["add", branch]).assert().success();
+    let worktree_path = repo.worktree_path_for(branch);
+
+    std::fs::write(worktree_path.join("README.md"), "feature side").unwrap();
+    common::run_git(&worktree_path, &["commit", "-q", "-am", "feature edit"]);
+    std::fs::write(repo.path().join("README.md"), "main side").unwrap();
+    common::run_git(repo.path(), &["commit", "-q", "-am", "main edit"]);
+
+    let merge = std::process::Command::new("git")
+        .current_dir(&worktree_path)
+        .args(["merge", "main"])
+        .output()
+        .unwrap();
+    assert!(!merge.status.success(), "merge should stop on the conflict");
+    std::fs::write(worktree_path.join("staged.txt"), "staged").unwrap();
+    common::run_git(&worktree_path, &["add", "staged.txt"]);
+    std::fs::write(worktree_path.join("untracked.txt"), "untracked").unwrap();
+
+    let output = repo
+        .command()
+        .args(["list", "--json", "--json-version", "2"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let entry = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["branch"] == branch)
+        .unwrap();
+    assert_eq!(entry["status"], "dirty");
+    assert_eq!(entry["changes"]["staged"], 1);
+    assert_eq!(entry["changes"]["untracked"], 1);
+    assert_eq!(entry["changes"]["conflicted"], 1);
+    assert_eq!(entry["operation"], "merge");
+
+    let main = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["is_main"] == true)
+        .unwrap();
+    assert_eq!(main["changes"]["staged"], 0);
+    assert!(main["operation"].is_null());
+
+    let table = String::from_utf8(
+        repo.command()
+            .arg("list")
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone(),
+    )
+    .unwrap();
+    assert!(table.contains("CHANGES"));
+    assert!(table.contains("+1 ?1 =1 MERGING"), "table:\n{table}");
+}
+
+#[test]
+fn list_keeps_worktree_order_and_supports_no_status() {
+    let repo = TestRepo::new();
+    let branches = [
+        "feature/c",
+        "feature/a",
+        "feature/d",
+        "feature/b",
+        "feature/e",
+    ];
+    for branch in branches {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+    std::fs::write(repo.worktree_path_for("feature/d").join("dirty.txt"), "x").unwrap();
+
+    let porcelain = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(["worktree", "list", "--porcelain"])
+        .output()
+        .unwrap();
+    let porcelain = String::from_utf8(porcelain.stdout).unwrap();
+    let expected: Vec<String> = porcelain
+        .lines()
+        .filter_map(|line| line.strip_prefix("worktree "))
+        .map(|path| {
+            normalize_path(std::path::Path::new(path))
+                .display()
+                .to_string()
+        })
+        .collect();
+
+    for args in [
+        &["list", "--json"][..],
+        &["list", "--json", "--no-status"][..],
+        &["list", "--json", "--json-version", "2"][..],
+        &["list", "--json", "--json-version", "2", "--no-status"][..],
+    ] {
+        let output = repo
+            .command()
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        let entries = entries.as_array().unwrap();
+        let paths: Vec<String> = entries
+            .iter()
+            .map(|e| {
+                normalize_path(std::path::Path::new(e["abs_path"].as_str().unwrap()))
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(paths, expected);
+
+        let dirty = entries.iter().find(|e| e["branch"] == "feature/d").unwrap();
+        let v2 = args.contains(&"2");
+        match (args.contains(&"--no-status"), v2) {
+            // Version 1 keeps `status` a string.
+            (true, false) => assert_eq!(dirty["status"], "-"),
+            (true, true) => {
+                assert!(dirty["status"].is_null());
+                assert!(dirty["changes"].is_null());
+            }
+            (false, _) => assert_eq!(dirty["status"], "dirty"),
+        }
+        if v2 && !args.contains(&"--no-status") {
+            assert_eq!(dirty["changes"]["untracked"], 1);
+        }
+    }
+
+    let table = String::from_utf8(
+        repo.command()
+            .args(["list", "--no-status"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone(),
+    )
+    .unwrap();
+    assert!(!table.contains("dirty"), "table:\n{table}");
+}
+
+#[test]
+fn list_cached_reuses_status_until_head_or_index_changes() {
+    let repo = TestRepo::new();
+    let branch = "feature/cache";
+    repo.create_branch(branch);
+    repo.command().args(["add", branch]).assert().success();
+    let worktree_path = repo.worktree_path_for(branch);
+    let cache_file = repo.path().join(".git").join("wtw").join("status-cache");
+
+    let status_of = |args: &[&str]| -> String {
+        let output = repo
+            .command()
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        let entry = entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["branch"] == branch)
+            .unwrap()
+            .clone();
+        entry["status"].as_str().unwrap().to_string()
+    };
+
+    assert_eq!(status_of(&["list", "--json", "--cached"]), "clean");
+    assert!(cache_file.exists());
+
+    // Untracked files leave HEAD and the index alone, so the cache still hits.
+    std::fs::write(worktree_path.join("new.txt"), "new").unwrap();
+    assert_eq!(status_of(&["list", "--json", "--cached"]), "clean");
+    assert_eq!(status_of(&["list", "--json"]), "dirty");
+
+    common::run_git(&worktree_path, &["add", "new.txt"]);
+    assert_eq!(status_of(&["list", "--json", "--cached"]), "dirty");
+
+    repo.create_branch("feature/other");
+    repo.command()
+        .args(["add", "feature/other"])
+        .assert()
+        .success();
+    assert!(!cache_file.exists(), "add should invalidate the cache");
+
+    status_of(&["list", "--json", "--cached"]);
+    assert!(cache_file.exists());
+    repo.command()
+        .args(["remove", "feature/other"])
+        .assert()
+        .success();
+    assert!(!cache_file.exists(), "remove should invalidate the cache");
+
+    repo.command()
+        .args(["list", "--cached", "--no-status"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn list_filters_and_sorts_table_and_json_alike() {
+    let repo = TestRepo::new();
+    for branch in ["feature/beta", "feature/alpha", "bugfix/gamma"] {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+    std::fs::write(
+        repo.worktree_path_for("feature/beta").join("dirty.txt"),
+        "x",
+    )
+    .unwrap();
+    repo.command()
+        .args(["lock", "bugfix/gamma"])
+        .assert()
+        .success();
+    let outside = repo.path().join("outside-wt");
+    repo.git(&[
+        "worktree",
+        "add",
+        "-q",
+        "-b",
+        "outside",
+        outside.to_str().unwrap(),
+    ]);
+
+    let names = |args: &[&str]| -> Vec<String> {
+        let output = repo
+            .command()
+            .arg("list")
+            .args(args)
+            .arg("--names-only")
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    };
+    let json_names = |args: &[&str]| -> Vec<String> {
+        let output = repo
+            .command()
+            .arg("list")
+            .args(args)
+            .arg("--json")
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let sorted = ["--filter", "branch~feature/*", "--sort", "name"];
+    assert_eq!(names(&sorted), ["feature/alpha", "feature/beta"]);
+    assert_eq!(json_names(&sorted), ["feature/alpha", "feature/beta"]);
+
+    let reversed = ["--filter", "managed", "--sort", "branch", "--reverse"];
+    assert_eq!(
+        names(&reversed),
+        ["@", "feature/beta", "feature/alpha", "bugfix/gamma"]
+    );
+    assert_eq!(json_names(&reversed), names(&reversed));
+
+    assert_eq!(
+        names(&["--filter", "status=dirty", "--filter", "branch~feature/*"]),
+        ["feature/beta"]
+    );
+    assert_eq!(names(&["--filter", "locked"]), ["bugfix/gamma"]);
+
+    let alpha = repo.worktree_path_for("feature/alpha");
+    std::fs::write(alpha.join("alpha.txt"), "alpha").unwrap();
+    common::run_git(&alpha, &["add", "alpha.txt"]);
+    let commit = std::process::Command::new("git")
+        .current_dir(&alpha)
+        .env("GIT_COMMITTER_DATE", "@4102444800 +0000")
+        .args(["commit", "-q", "-m", "alpha"])
+        .status()
+        .unwrap();
+    assert!(commit.success());
+    let by_commit = ["--filter", "managed", "--sort", "last-commit"];
+    assert_eq!(names(&by_commit)[0], "feature/alpha");
+    let output = repo
+        .command()
+        .args(["list", "--json", "--json-version", "2"])
+        .args(by_commit)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert!(
+        entries[0].get("last_commit").is_none(),
+        "--sort alone adds no fields"
+    );
+    assert_eq!(names(&["--filter", "!managed"]), ["outside-wt"]);
+    assert_eq!(
+        names(&[
+            "--filter", "managed", "--filter", "!locked", "--filter", "clean", "--filter", "!main",
+        ]),
+        ["feature/alpha"]
+    );
+
+    let table = String::from_utf8(
+        repo.command()
+            .args(["list", "--filter", "status=dirty", "--filter", "!main"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone(),
+    )
+    .unwrap();
+    assert!(table.contains("feature/beta"));
+    assert!(!table.contains("feature/alpha"));
+
+    repo.command()
+        .args(["list", "--filter", "colour=red"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid --filter 'colour=red'"));
+    repo.command()
+        .args(["list", "--filter", "dirty", "--no-status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cannot be combined with --no-status",
+        ));
+}
+
+#[test]
+fn list_supports_ndjson_csv_tsv_templates_and_columns() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/auth");
+    repo.command()
+        .args(["add", "feature/auth"])
+        .assert()
+        .success();
+    repo.command()
+        .args(["lock", "feature/auth", "--reason", "on hold, ask first"])
+        .assert()
+        .success();
+
+    let stdout = |args: &[&str]| -> String {
+        let output = repo
+            .command()
+            .arg("list")
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(output).unwrap()
+    };
+
+    let ndjson = stdout(&["--format", "ndjson"]);
+    let lines: Vec<serde_json::Value> = ndjson
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["name"], "feature/auth");
+
+    let csv = stdout(&["--format", "csv", "--columns", "path,branch,locked"]);
+    let csv_lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(csv_lines[0], "path,branch,locked");
+    assert_eq!(csv_lines[1], "@,main,");
+    assert_eq!(
+        csv_lines[2],
+        "feature/auth,feature/auth,\"on hold, ask first\""
+    );
+
+    let tsv = stdout(&["--format", "tsv", "--filter", "!main"]);
+    let header: Vec<&str> = tsv.lines().next().unwrap().split('\t').collect();
+    assert_eq!(
+        header,
+        [
+            "path", "branch", "head", "status", "changes", "upstream", "locked", "prunable",
+            "abs_path"
+        ]
+    );
+    assert!(
+        tsv.lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("feature/auth\tfeature/auth\t")
+    );
+
+    let template = stdout(&[
+        "--template",
+        r"{{name}}\t{{branch}}\t{{changes.untracked}}",
+        "--json-version",
+        "2",
+        "--filter",
+        "!main",
+    ]);
+    assert_eq!(template, "feature/auth\tfeature/auth\t0\n");
+
+    let table = stdout(&["--columns", "branch,path"]);
+    assert!(table.lines().next().unwrap().starts_with("BRANCH"));
+    assert!(table.contains("@*"));
+    assert!(!table.contains("ABS_PATH"));
+
+    repo.command()
+        .args(["list", "--columns", "colour"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown column 'colour'"));
+    repo.command()
+        .args(["list", "--format", "ndjson", "--columns", "path"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--columns only applies"));
+    repo.command()
+        .args(["list", "--template", "{{colour}}"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown template field 'colour'"));
+    repo.command()
+        .args(["list", "--template", "{{changes.untracked}}"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown template field"));
+}
+
+#[test]
+fn list_json_version_2_adds_full_head_relative_path_and_flags() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/auth");
+    repo.command()
+        .args(["add", "feature/auth"])
+        .assert()
+        .success();
+
+    let json = |args: &[&str]| -> Vec<serde_json::Value> {
+        let output = repo
+            .command()
+            .args(["list", "--json"])
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        serde_json::from_slice(&output).unwrap()
+    };
+
+    let v2 = json(&["--json-version", "2"]);
+    assert_eq!(v2.len(), 2);
+    let main = &v2[0];
+    assert_eq!(main["path"], ".");
+    assert_eq!(main["head"].as_str().unwrap().len(), 40);
+    assert_eq!(main["head_short"].as_str().unwrap().len(), 8);
+    assert!(
+        main["head"]
+            .as_str()
+            .unwrap()
+            .starts_with(main["head_short"].as_str().unwrap())
+    );
+    assert_eq!(main["is_main"], true);
+    assert_eq!(main["is_managed"], true);
+    assert_eq!(main["is_detached"], false);
+    let feature = &v2[1];
+    assert_eq!(feature["name"], "feature/auth");
+    assert_eq!(feature["path"], "worktree/feature/auth");
+    assert_eq!(feature["is_managed"], true);
+    assert_eq!(feature["locked"], serde_json::Value::Null);
+    assert_eq!(feature["prunable"], serde_json::Value::Null);
+
+    let v1 = json(&[]);
+    assert_eq!(v1, json(&["--json-version", "1"]));
+    assert_eq!(v1[1]["path"], "feature/auth");
+    assert_eq!(v1[1]["head"].as_str().unwrap().len(), 8);
+    assert!(v1[1].get("is_managed").is_none());
+
+    repo.command()
+        .args(["list", "--json-version", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--json-version only applies"));
+}
+
+#[test]
+fn list_json_version_1_is_frozen() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/auth");
+    repo.command()
+        .args(["add", "feature/auth"])
+        .assert()
+        .success();
+    let worktree_path = repo.worktree_path_for("feature/auth");
+    std::fs::write(worktree_path.join("dirty.txt"), "dirty").unwrap();
+    common::run_git(&worktree_path, &["branch", "--set-upstream-to=main"]);
+    repo.command()
+        .args(["lock", "feature/auth", "--reason", "frozen"])
+        .assert()
+        .success();
+    // Keep the main worktree clean despite the worktree directory inside it.
+    std::fs::write(repo.path().join(".git/info/exclude"), "worktree/\n").unwrap();
+
+    let head = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(["rev-parse", "--short=8", "HEAD"])
+        .output()
+        .unwrap();
+    let head = String::from_utf8(head.stdout).unwrap().trim().to_string();
+
+    for args in [&[][..], &["--no-status"][..]] {
+        let output = repo
+            .command()
+            .args(["list", "--json"])
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let stdout = String::from_utf8(output).unwrap();
+
+        // The keys and their order are part of the format.
+        let keys: Vec<&str> = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("    \""))
+            .filter_map(|line| line.split_once('"').map(|(key, _)| key))
+            .take(9)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "name",
+                "branch",
+                "head",
+                "status",
+                "upstream",
+                "path",
+                "abs_path",
+                "is_main",
+                "is_current"
+            ]
+        );
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&stdout).unwrap();
+        assert_eq!(entries.len(), 2);
+        for (entry, path) in entries.iter().zip([repo.path(), &worktree_path]) {
+            let abs_path = normalize_path(Path::new(entry["abs_path"].as_str().unwrap()));
+            assert_eq!(abs_path, normalize_path(path));
+        }
+        let status = if args.contains(&"--no-status") {
+            ["-", "-"]
+        } else {
+            ["clean", "dirty"]
+        };
+        let expected = serde_json::json!([
+            {
+                "name": "@",
+                "branch": "main",
+                "head": head,
+                "status": status[0],
+                "upstream": null,
+                "path": "@",
+                "abs_path": entries[0]["abs_path"],
+                "is_main": true,
+                "is_current": true
+            },
+            {
+                "name": "feature/auth",
+                "branch": "feature/auth",
+                "head": head,
+                "status": status[1],
+                "upstream": "main",
+                "path": "feature/auth",
+                "abs_path": entries[1]["abs_path"],
+                "is_main": false,
+                "is_current": false
+            }
+        ]);
+        assert_eq!(serde_json::Value::Array(entries), expected);
+    }
+
+    repo.command()
+        .args(["list", "--json", "--long", "--json-version", "1"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn list_table_aligns_wide_characters_and_colors_on_request() {
+    let repo = TestRepo::new();
+    repo.create_branch("機能/認証");
+    repo.command().args(["add", "機能/認証"]).assert().success();
+
+    let stdout = |args: &[&str]| -> String {
+        let output = repo
+            .command()
+            .args(["list", "--columns", "path,branch,status"])
+            .args(args)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(output).unwrap()
+    };
+
+    // "機能/認証" takes nine cells, so PATH is nine cells wide.
+    let plain = stdout(&[]);
+    let lines: Vec<&str> = plain.lines().collect();
+    assert!(lines[0].starts_with("PATH      BRANCH"), "{plain}");
+    assert!(lines[2].starts_with("@*        main"), "{plain}");
+    assert!(lines[3].starts_with("機能/認証 機能/認証"), "{plain}");
+    assert!(!plain.contains('\u{1b}'));
+
+    let colored = stdout(&["--color", "always"]);
+    assert!(colored.contains('\u{1b}'));
+    assert!(colored.lines().next().unwrap().starts_with("PATH"));
+
+    let output = repo
+        .command()
+        .env("NO_COLOR", "1")
+        .args(["list", "--color", "auto"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(!String::from_utf8(output).unwrap().contains('\u{1b}'));
+}
+
+#[test]
+fn list_marks_prunable_and_unmanaged_worktrees_and_disambiguates_names() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/gone");
+    repo.command()
+        .args(["add", "feature/gone"])
+        .assert()
+        .success();
+    std::fs::remove_dir_all(repo.worktree_path_for("feature/gone")).unwrap();
+    for (branch, dir) in [
+        ("scratch-a", "scratch/a/tmp"),
+        ("scratch-b", "scratch/b/tmp"),
+    ] {
+        let path = repo.path().join(dir);
+        repo.git(&["worktree", "add", "-b", branch, path.to_str().unwrap()]);
+    }
+
+    let output = repo
+        .command()
+        .args(["list", "--json", "--json-version", "2"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output).unwrap();
+    let by_branch = |branch: &str| {
+        entries
+            .iter()
+            .find(|entry| entry["branch"] == branch)
+            .unwrap()
+            .clone()
+    };
+    let gone = by_branch("feature/gone");
+    assert!(gone["prunable"].is_string());
+    assert_eq!(gone["status"], serde_json::Value::Null);
+    assert_eq!(gone["is_managed"], true);
+    let sep = std::path::MAIN_SEPARATOR;
+    let a = by_branch("scratch-a");
+    assert_eq!(a["name"], format!("a{sep}tmp"));
+    assert_eq!(a["is_managed"], false);
+    assert_eq!(by_branch("scratch-b")["name"], format!("b{sep}tmp"));
+
+    // Version 1 keeps the plain directory names.
+    let output = repo
+        .command()
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v1: Vec<serde_json::Value> = serde_json::from_slice(&output).unwrap();
+    let v1_names: Vec<&str> = v1
+        .iter()
+        .filter(|entry| entry["branch"].as_str().unwrap().starts_with("scratch-"))
+        .map(|entry| entry["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(v1_names, ["tmp", "tmp"]);
+    let gone = v1
+        .iter()
+        .find(|entry| entry["branch"] == "feature/gone")
+        .unwrap();
+    assert_eq!(gone["status"], "-");
+
+    let output = repo
+        .command()
+        .args(["list", "--columns", "path,prunable,managed"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let table = String::from_utf8(output).unwrap();
+    assert!(table.contains(&format!("a{sep}tmp (unmanaged)")), "{table}");
+    let gone_line = table
+        .lines()
+        .find(|line| line.starts_with(&format!("feature{sep}gone")))
+        .unwrap();
+    assert!(!gone_line.contains(" - "), "{table}");
+    assert!(gone_line.trim_end().ends_with("yes"), "{table}");
+
+    // A missing worktree has no status, so status filters leave it out.
+    repo.command()
+        .args(["list", "--names-only", "--filter", "!clean"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("gone").not());
+
+    // The disambiguated name resolves to that worktree.
+    repo.command()
+        .args(["remove", "--include-unmanaged", &format!("b{sep}tmp")])
+        .assert()
+        .success();
+    assert!(repo.path().join("scratch/a/tmp").exists());
+    assert!(!repo.path().join("scratch/b/tmp").exists());
+}
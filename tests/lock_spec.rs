@@ -0,0 +1,116 @@
+mod common;
+
+use common::TestRepo;
+use predicates::prelude::*;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct ListEntry {
+    branch: Option<String>,
+    locked: Option<String>,
+}
+
+#[test]
+fn lock_and_unlock_round_trip_is_visible_in_list() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/lock");
+    repo.command()
+        .args(["add", "feature/lock"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["lock", "feature/lock", "--reason", "demo tomorrow"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Locked worktree 'feature/lock': demo tomorrow",
+        ));
+
+    assert_eq!(
+        locked_reason(&repo, "feature/lock").as_deref(),
+        Some("demo tomorrow")
+    );
+    repo.command()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LOCKED"))
+        .stdout(predicate::str::contains("demo tomorrow"));
+
+    repo.command()
+        .args(["unlock", "feature/lock"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unlocked worktree 'feature/lock'"));
+    assert_eq!(locked_reason(&repo, "feature/lock"), None);
+}
+
+#[test]
+fn unlock_rejects_worktree_that_is_not_locked() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/open");
+    repo.command()
+        .args(["add", "feature/open"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["unlock", "feature/open"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not locked"));
+}
+
+#[test]
+fn remove_refuses_locked_worktree_unless_forced_twice() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/pinned");
+    repo.command()
+        .args(["add", "feature/pinned"])
+        .assert()
+        .success();
+    repo.command()
+        .args(["lock", "feature/pinned", "--reason", "keep me"])
+        .assert()
+        .success();
+    let path = repo.worktree_path_for("feature/pinned");
+
+    for args in [
+        vec!["remove", "feature/pinned"],
+        vec!["remove", "--force", "feature/pinned"],
+    ] {
+        let output = repo
+            .command()
+            .args(&args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("locked: keep me"))
+            .get_output()
+            .clone();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(path.exists(), "locked worktree must survive {:?}", args);
+    }
+
+    repo.command()
+        .args(["remove", "-ff", "feature/pinned"])
+        .assert()
+        .success();
+    assert!(!path.exists());
+}
+
+fn locked_reason(repo: &TestRepo, branch: &str) -> Option<String> {
+    let output = repo
+        .command()
+        .args(["list", "--json", "--json-version", "2"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: Vec<ListEntry> = serde_json::from_slice(&output).unwrap();
+    entries
+        .into_iter()
+        .find(|entry| entry.branch.as_deref() == Some(branch))
+        .and_then(|entry| entry.locked)
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::{TestRepo, branch_exists, normalize_path};
+use predicates::prelude::*;
+use std::path::Path;
+
+#[test]
+fn move_relocates_worktree_and_renames_branch() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/old");
+    repo.command()
+        .args(["add", "feature/old"])
+        .assert()
+        .success();
+    let old_path = repo.worktree_path_for("feature/old");
+    let new_path = repo.worktree_path_for("feature/new");
+
+    repo.command()
+        .args(["move", "feature/old", "feature/new"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed branch 'feature/old' to 'feature/new'",
+        ))
+        .stdout(predicate::str::contains("Moved worktree 'feature/old'"));
+
+    assert!(!old_path.exists());
+    assert!(new_path.exists());
+    assert!(!branch_exists(repo.path(), "feature/old"));
+    assert!(branch_exists(repo.path(), "feature/new"));
+
+    let output = repo
+        .command()
+        .args(["cd", "feature/new"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let cd_path = normalize_path(Path::new(String::from_utf8(output).unwrap().trim()));
+    assert_eq!(cd_path, normalize_path(&new_path));
+}
+
+#[test]
+fn rename_alias_can_keep_branch_name() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/keep");
+    repo.command()
+        .args(["add", "feature/keep"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["rename", "--keep-branch", "feature/keep", "kept"])
+        .assert()
+        .success();
+
+    assert!(repo.worktree_path_for("kept").exists());
+    assert!(branch_exists(repo.path(), "feature/keep"));
+    assert!(
+        !repo.worktrees_dir().join("feature").exists(),
+        "empty parent directories should be cleaned up"
+    );
+}
+
+#[test]
+fn move_refuses_locked_worktree() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/locked");
+    repo.command()
+        .args(["add", "feature/locked"])
+        .assert()
+        .success();
+    let path = repo.worktree_path_for("feature/locked");
+    repo.git(&[
+        "worktree",
+        "lock",
+        "--reason",
+        "on a usb stick",
+        path.to_str().unwrap(),
+    ]);
+
+    repo.command()
+        .args(["move", "feature/locked", "feature/elsewhere"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is locked: on a usb stick"));
+    assert!(path.exists());
+    assert!(branch_exists(repo.path(), "feature/locked"));
+}
+
+#[test]
+fn move_refuses_conflicting_destination() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/a");
+    repo.create_branch("feature/b");
+    repo.command().args(["add", "feature/a"]).assert().success();
+    repo.command().args(["add", "feature/b"]).assert().success();
+
+    repo.command()
+        .args(["move", "feature/a", "feature/b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "worktree for branch 'feature/b' already exists",
+        ));
+    assert!(repo.worktree_path_for("feature/a").exists());
+    assert!(branch_exists(repo.path(), "feature/a"));
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{TestRepo, normalize_path};
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn stdout_of(assert: assert_cmd::assert::Assert) -> String {
+    String::from_utf8(assert.get_output().stdout.clone())
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn path_prints_files_inside_a_worktree() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/auth");
+    repo.command()
+        .args(["add", "feature/auth"])
+        .assert()
+        .success();
+    let feature = normalize_path(&repo.worktree_path_for("feature/auth"));
+
+    let root = stdout_of(
+        repo.command()
+            .args(["path", "feature/auth"])
+            .assert()
+            .success(),
+    );
+    assert_eq!(normalize_path(Path::new(&root)), feature);
+
+    let file = stdout_of(
+        repo.command()
+            .args(["path", "auth:src/main.rs"])
+            .assert()
+            .success(),
+    );
+    assert_eq!(PathBuf::from(file), feature.join("src").join("main.rs"));
+
+    let relative = stdout_of(
+        repo.command()
+            .args(["path", "--relative", "feature/auth:src/main.rs"])
+            .assert()
+            .success(),
+    );
+    let expected: PathBuf = ["worktree", "feature", "auth", "src", "main.rs"]
+        .iter()
+        .collect();
+    assert_eq!(PathBuf::from(relative), expected);
+
+    let up = stdout_of(
+        repo.command_in(&feature)
+            .args(["path", "--relative", "@:README.md"])
+            .assert()
+            .success(),
+    );
+    let expected: PathBuf = ["..", "..", "..", "README.md"].iter().collect();
+    assert_eq!(PathBuf::from(up), expected);
+}
+
+#[test]
+fn cd_accepts_a_directory_inside_the_worktree() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/auth");
+    repo.command()
+        .args(["add", "feature/auth"])
+        .assert()
+        .success();
+    let feature = repo.worktree_path_for("feature/auth");
+    fs::create_dir_all(feature.join("src").join("api")).unwrap();
+
+    let dir = stdout_of(
+        repo.command()
+            .args(["cd", "feature/auth:src/api"])
+            .assert()
+            .success(),
+    );
+    assert_eq!(
+        normalize_path(Path::new(&dir)),
+        normalize_path(&feature.join("src").join("api"))
+    );
+
+    repo.command()
+        .args(["cd", "feature/auth:missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'missing' is not a directory in worktree 'feature/auth'",
+        ));
+}
+
+#[test]
+fn inner_paths_cannot_leave_the_worktree() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/auth");
+    repo.command()
+        .args(["add", "feature/auth"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["path", "feature/auth:../../elsewhere"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("stay inside it"));
+    repo.command()
+        .args(["cd", "feature/auth:src/../.."])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("stay inside it"));
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::TestRepo;
+use predicates::prelude::*;
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn prune_reports_and_removes_stale_metadata() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/gone");
+    repo.command()
+        .args(["add", "feature/gone"])
+        .assert()
+        .success();
+    let worktree_path = repo.worktree_path_for("feature/gone");
+    fs::remove_dir_all(&worktree_path).unwrap();
+
+    repo.command()
+        .args(["prune", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would prune worktree metadata"))
+        .stdout(predicate::str::contains("gone"));
+    assert!(worktree_list(&repo).contains("feature/gone"));
+
+    repo.command()
+        .args(["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pruned worktree metadata"));
+    assert!(!worktree_list(&repo).contains("feature/gone"));
+    assert!(
+        !worktree_path.parent().unwrap().exists(),
+        "the emptied parent is an orphan of its own"
+    );
+}
+
+#[test]
+fn prune_deletes_orphaned_directories_only_when_confirmed() {
+    let repo = TestRepo::new();
+    let orphan = repo.worktree_path_for("interrupted");
+    fs::create_dir_all(&orphan).unwrap();
+    let gitdir = repo
+        .path()
+        .join(".git")
+        .join("worktrees")
+        .join("interrupted");
+    fs::write(
+        orphan.join(".git"),
+        format!("gitdir: {}\n", gitdir.display()),
+    )
+    .unwrap();
+    fs::write(orphan.join("partial.txt"), "partial").unwrap();
+
+    repo.command()
+        .args(["prune", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Found orphaned directory"))
+        .stdout(predicate::str::contains("interrupted"));
+    assert!(orphan.exists(), "dry run must not delete anything");
+
+    repo.command()
+        .arg("prune")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "refusing to delete 1 orphaned directory without confirmation",
+        ))
+        .stderr(predicate::str::contains("Re-run with --yes"));
+    assert!(
+        orphan.exists(),
+        "non-interactive prune must not delete without --yes"
+    );
+
+    repo.command()
+        .args(["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed orphaned directory"));
+    assert!(!orphan.exists(), "orphaned directory should be removed");
+}
+
+#[test]
+fn prune_removes_parents_left_empty_by_nested_orphans() {
+    let repo = TestRepo::new();
+    let base_dir = repo.path().join("worktree");
+    fs::create_dir_all(base_dir.join("group").join("empty")).unwrap();
+    fs::create_dir_all(base_dir.join("kept").join("empty")).unwrap();
+    fs::write(base_dir.join("kept").join("notes.txt"), "notes").unwrap();
+
+    repo.command()
+        .args(["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed orphaned directory"));
+    assert!(
+        !base_dir.join("group").exists(),
+        "emptied parent should be removed"
+    );
+    assert!(!base_dir.join("kept").join("empty").exists());
+    assert!(base_dir.join("kept").join("notes.txt").exists());
+    assert!(base_dir.exists(), "base_dir itself must stay");
+
+    repo.command()
+        .args(["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to prune"));
+}
+
+#[test]
+fn prune_leaves_registered_worktrees_alone() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/kept");
+    repo.command()
+        .args(["add", "feature/kept"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to prune"));
+    assert!(repo.worktree_path_for("feature/kept").exists());
+}
+
+fn worktree_list(repo: &TestRepo) -> String {
+    let output = Command::new("git")
+        .current_dir(repo.path())
+        .args(["worktree", "list", "--porcelain"])
+        .output()
+        .expect("git worktree list");
+    String::from_utf8(output.stdout).unwrap()
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{TestRepo, normalize_path};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+
+fn cd_output(output: &[u8]) -> PathBuf {
+    normalize_path(Path::new(String::from_utf8_lossy(output).trim()))
+}
+
+fn add_worktrees(repo: &TestRepo, branches: &[&str]) {
+    for branch in branches {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+}
+
+#[test]
+fn cd_dash_returns_to_the_previous_worktree() {
+    let repo = TestRepo::new();
+    add_worktrees(&repo, &["feature/auth"]);
+    let feature = repo.worktree_path_for("feature/auth");
+
+    repo.command()
+        .args(["cd", "-"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no previous worktree in history"));
+
+    repo.command()
+        .args(["cd", "feature/auth"])
+        .assert()
+        .success();
+
+    let back = repo
+        .command_in(&feature)
+        .args(["cd", "-"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(cd_output(&back), normalize_path(repo.path()));
+
+    let forth = repo
+        .command()
+        .args(["cd", "-"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(cd_output(&forth), normalize_path(&feature));
+}
+
+#[test]
+fn history_breaks_fuzzy_ties_for_cd() {
+    let repo = TestRepo::new();
+    add_worktrees(&repo, &["feature/billing", "feature/bugfix"]);
+
+    repo.command()
+        .args(["cd", "b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is ambiguous"));
+
+    repo.command()
+        .args(["cd", "feature/bugfix"])
+        .assert()
+        .success();
+
+    let output = repo
+        .command()
+        .args(["cd", "b"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(
+        cd_output(&output),
+        normalize_path(&repo.worktree_path_for("feature/bugfix"))
+    );
+
+    // remove never lets the history pick a worktree to delete.
+    repo.command()
+        .args(["remove", "b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("worktree 'b' is ambiguous"));
+}
+
+#[test]
+fn recent_ranks_visited_worktrees() {
+    let repo = TestRepo::new();
+    add_worktrees(&repo, &["feature/auth", "feature/billing", "feature/idle"]);
+
+    repo.command()
+        .arg("recent")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No worktree history yet"));
+
+    for target in ["feature/auth", "feature/billing", "feature/billing"] {
+        repo.command().args(["cd", target]).assert().success();
+    }
+
+    let output = repo
+        .command()
+        .arg("recent")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    let billing = stdout.find("feature/billing").expect("billing listed");
+    let auth = stdout.find("feature/auth").expect("auth listed");
+    assert!(billing < auth, "unexpected order:\n{stdout}");
+    assert!(!stdout.contains("feature/idle"));
+
+    let output = repo
+        .command()
+        .args(["recent", "--json", "--limit", "1"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let rows: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let rows = rows.as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["visits"], 2);
+}
+
+#[test]
+fn failed_cd_is_not_recorded() {
+    let repo = TestRepo::new();
+    add_worktrees(&repo, &["feature/auth"]);
+
+    repo.command()
+        .args(["cd", "feature/auth:missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a directory"));
+
+    repo.command()
+        .args(["cd", "-"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no previous worktree in history"));
+    repo.command()
+        .arg("recent")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/auth").not());
+}
@@ -1,7 +1,8 @@
 mod common;
 
-use common::TestRepo;
+use common::{TestRepo, branch_exists, normalize_path};
 use predicates::prelude::*;
+use std::fs;
 use std::path::Path;
 
 #[test]
@@ -61,7 +62,7 @@ defaults:
 }
 
 #[test]
-fn remove_rejects_current_worktree() {
+fn remove_current_worktree_points_to_main_worktree() {
     let repo = TestRepo::new();
     repo.create_branch("feature/current");
     repo.command()
@@ -69,14 +70,92 @@ fn remove_rejects_current_worktree() {
         .assert()
         .success();
     let worktree_path = repo.worktree_path_for("feature/current");
+    let main_path = normalize_path(repo.path());
 
     repo.command_in(&worktree_path)
-        .args(["remove", "feature/current"])
+        .args(["remove", "--print-fallback", "feature/current"])
         .assert()
-        .failure()
-        .stderr(predicate::str::contains("cannot remove the current worktree"));
+        .success()
+        .stdout(format!("{}\n", main_path.display()));
+    assert!(worktree_path.exists(), "--print-fallback must not remove anything");
+
+    repo.command()
+        .args(["remove", "--print-fallback", "feature/current"])
+        .assert()
+        .success()
+        .stdout("");
+
+    repo.command_in(&worktree_path)
+        .args(["remove", "--with-branch", "feature/current"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed branch 'feature/current'"))
+        .stdout(predicate::str::contains(format!(
+            "The current worktree was removed; continue in {}",
+            main_path.display()
+        )));
+
+    assert!(!worktree_path.exists());
+}
+
+#[test]
+fn remove_current_worktree_uses_configured_fallback_dir() {
+    let repo = TestRepo::new();
+    repo.write_config(
+        r#"version: "1.0"
+defaults:
+  base_dir: worktree
+  fallback_dir: worktree
+"#,
+    );
+    repo.create_branch("feature/here");
+    repo.command()
+        .args(["add", "feature/here"])
+        .assert()
+        .success();
+    let worktree_path = repo.worktree_path_for("feature/here");
+
+    repo.command_in(&worktree_path)
+        .args(["remove", "--print-fallback", "feature/here"])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{}\n",
+            normalize_path(repo.path()).join("worktree").display()
+        ));
+}
+
+#[test]
+fn remove_batch_including_current_worktree_removes_every_target() {
+    let repo = TestRepo::new();
+    for branch in ["feat-a", "feat-b"] {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+    let current = repo.worktree_path_for("feat-a");
+    let other = repo.worktree_path_for("feat-b");
+
+    let output = repo
+        .command_in(&current)
+        .args(["remove", "--yes", "--with-branch", "feat-a", "feat-b"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed branch 'feat-a'"))
+        .stdout(predicate::str::contains("Removed branch 'feat-b'"))
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
 
-    assert!(worktree_path.exists(), "current worktree must remain intact");
+    // The current worktree goes last, after everything that needs Git to
+    // run in it.
+    let removed_b = stdout.find("Removed worktree 'feat-b'").unwrap();
+    let removed_a = stdout.find("Removed worktree 'feat-a'").unwrap();
+    assert!(removed_b < removed_a, "{stdout}");
+    assert!(!current.exists());
+    assert!(!other.exists());
+    assert!(!branch_exists(repo.path(), "feat-a"));
+    assert!(!branch_exists(repo.path(), "feat-b"));
 }
 
 #[test]
@@ -91,9 +170,153 @@ fn remove_force_branch_requires_with_branch_flag() {
         ));
 }
 
-fn branch_exists(repo_path: &Path, branch: &str) -> bool {
+#[test]
+fn remove_accepts_multiple_targets_and_globs() {
+    let repo = TestRepo::new();
+    for branch in ["feat-a", "experiment/one", "experiment/two", "keep"] {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+
+    // Bulk removal needs --yes when there is no terminal to confirm on.
+    repo.command()
+        .args(["remove", "feat-a", "experiment/*"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("without confirmation"))
+        .stderr(predicate::str::contains("experiment"));
+    assert!(repo.worktree_path_for("feat-a").exists());
+
+    repo.command()
+        .args(["remove", "--yes", "feat-a", "experiment/*"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree 'feat-a'"))
+        .stdout(predicate::str::contains("experiment"));
+
+    assert!(!repo.worktree_path_for("feat-a").exists());
+    assert!(!repo.worktree_path_for("experiment/one").exists());
+    assert!(!repo.worktree_path_for("experiment/two").exists());
+    assert!(repo.worktree_path_for("keep").exists());
+}
+
+#[test]
+fn remove_reports_each_failed_target_and_exits_non_zero() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/ok");
+    repo.command().args(["add", "feature/ok"]).assert().success();
+
+    let output = repo
+        .command()
+        .args(["remove", "--yes", "feature/ok", "ghost", "nothing/*"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Removed worktree 'feature/ok'"))
+        .stderr(predicate::str::contains("Failed to remove 'ghost'"))
+        .stderr(predicate::str::contains("no worktrees match 'nothing/*'"))
+        .stderr(predicate::str::contains("failed to remove 2 of 3 worktrees"))
+        .get_output()
+        .clone();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!repo.worktree_path_for("feature/ok").exists());
+}
+
+#[test]
+fn forced_remove_of_dirty_worktree_requires_yes_without_a_terminal() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/unsaved");
+    repo.command()
+        .args(["add", "feature/unsaved"])
+        .assert()
+        .success();
+    let path = repo.worktree_path_for("feature/unsaved");
+    fs::write(path.join("README.md"), "changed").unwrap();
+    fs::write(path.join("new.txt"), "new").unwrap();
+
+    repo.command()
+        .args(["remove", "--force", "feature/unsaved"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("without confirmation"))
+        .stderr(predicate::str::contains(
+            "feature/unsaved: 2 uncommitted files",
+        ))
+        .stderr(predicate::str::contains("--yes"));
+    assert!(path.exists(), "worktree must survive a refused removal");
+
+    repo.command()
+        .args(["remove", "--force", "--yes", "feature/unsaved"])
+        .assert()
+        .success();
+    assert!(!path.exists());
+}
+
+#[test]
+fn remove_with_remote_branch_deletes_upstream_after_confirmation() {
+    let repo = TestRepo::new();
+    let remote = tempfile::TempDir::new().unwrap();
+    let path = publish_branch(&repo, remote.path(), "feature/pub");
+
+    repo.command()
+        .args(["remove", "--with-remote-branch", "feature/pub"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "remote branch 'origin/feature/pub' will be deleted",
+        ));
+    assert!(path.exists());
+    assert!(remote_branch_exists(remote.path(), "feature/pub"));
+
+    repo.command()
+        .args(["remove", "--with-remote-branch", "--yes", "feature/pub"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed branch 'feature/pub'"))
+        .stdout(predicate::str::contains(
+            "Deleted remote branch 'origin/feature/pub'",
+        ));
+    assert!(!path.exists());
+    assert!(!remote_branch_exists(remote.path(), "feature/pub"));
+}
+
+#[test]
+fn remove_with_remote_branch_reports_rejected_push() {
+    let repo = TestRepo::new();
+    let remote = tempfile::TempDir::new().unwrap();
+    let path = publish_branch(&repo, remote.path(), "feature/kept");
+    common::run_git(remote.path(), &["config", "receive.denyDeletes", "true"]);
+
+    let output = repo
+        .command()
+        .args(["remove", "--with-remote-branch", "--yes", "feature/kept"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Removed branch 'feature/kept'"))
+        .stderr(predicate::str::contains(
+            "failed to delete remote branch 'origin/feature/kept'",
+        ))
+        .get_output()
+        .clone();
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(!path.exists());
+    assert!(remote_branch_exists(remote.path(), "feature/kept"));
+}
+
+fn publish_branch(repo: &TestRepo, remote: &Path, branch: &str) -> std::path::PathBuf {
+    common::run_git(remote, &["init", "-q", "--bare"]);
+    repo.git(&["remote", "add", "origin", remote.to_str().unwrap()]);
+    repo.create_branch(branch);
+    repo.command().args(["add", branch]).assert().success();
+    let path = repo.worktree_path_for(branch);
+    common::run_git(&path, &["push", "-q", "-u", "origin", branch]);
+    path
+}
+
+fn remote_branch_exists(remote: &Path, branch: &str) -> bool {
     std::process::Command::new("git")
-        .current_dir(repo_path)
+        .current_dir(remote)
         .args([
             "show-ref",
             "--verify",
@@ -105,3 +328,119 @@ fn branch_exists(repo_path: &Path, branch: &str) -> bool {
         .success()
 }
 
+#[test]
+fn remove_accepts_worktree_paths() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/by-path");
+    repo.command()
+        .args(["add", "feature/by-path"])
+        .assert()
+        .success();
+    let path = repo.worktree_path_for("feature/by-path");
+
+    repo.command()
+        .args(["remove", "worktree/feature/by-path/"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree"));
+    assert!(!path.exists());
+}
+
+#[test]
+fn remove_include_unmanaged_allows_worktrees_outside_base_dir() {
+    let repo = TestRepo::new();
+    let outside = tempfile::TempDir::new().unwrap();
+    let path = outside.path().join("elsewhere");
+    repo.git(&[
+        "worktree",
+        "add",
+        "-q",
+        "-b",
+        "elsewhere",
+        path.to_str().unwrap(),
+    ]);
+
+    repo.command()
+        .args(["remove", "elsewhere"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found under base_dir"))
+        .stderr(predicate::str::contains("--include-unmanaged"));
+    assert!(path.exists());
+
+    repo.command()
+        .args(["remove", "--include-unmanaged", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree"));
+    assert!(!path.exists());
+
+    repo.command()
+        .args(["remove", "--include-unmanaged", repo.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is the main worktree"));
+}
+
+#[test]
+fn remove_confirms_fuzzy_targets() {
+    let repo = TestRepo::new();
+    for branch in ["feature/auth-refactor", "feature/audit", "bugfix/login"] {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+
+    // A guessed worktree is never removed without confirmation.
+    repo.command()
+        .args(["remove", "authref"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("without confirmation"))
+        .stderr(predicate::str::contains(
+            "feature/auth-refactor: matched 'authref'",
+        ))
+        .stderr(predicate::str::contains("--yes"));
+    assert!(repo.worktree_path_for("feature/auth-refactor").exists());
+
+    repo.command()
+        .args(["remove", "au"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ambiguous"))
+        .stderr(predicate::str::contains("feature/audit"));
+
+    repo.command()
+        .args(["remove", "--yes", "authref"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed worktree 'feature/auth-refactor'",
+        ));
+    assert!(!repo.worktree_path_for("feature/auth-refactor").exists());
+    assert!(repo.worktree_path_for("feature/audit").exists());
+
+    // Exact names still need no confirmation.
+    repo.command()
+        .args(["remove", "login"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed worktree 'login'"));
+    assert!(!repo.worktree_path_for("bugfix/login").exists());
+}
+
+#[test]
+fn remove_without_target_requires_a_name_when_not_interactive() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/keep");
+    repo.command()
+        .args(["add", "feature/keep"])
+        .assert()
+        .success();
+
+    repo.command()
+        .arg("remove")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("worktree name is required"));
+    assert!(repo.worktree_path_for("feature/keep").exists());
+}
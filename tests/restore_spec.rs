@@ -0,0 +1,230 @@
+mod common;
+
+use common::TestRepo;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn forced_remove_backs_up_dirty_worktree_and_restore_recreates_it() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/dirty");
+    repo.command()
+        .args(["add", "feature/dirty"])
+        .assert()
+        .success();
+    let path = repo.worktree_path_for("feature/dirty");
+    fs::write(path.join("README.md"), "edited").unwrap();
+    fs::write(path.join("staged.txt"), "staged").unwrap();
+    common::run_git(&path, &["add", "staged.txt"]);
+    fs::create_dir_all(path.join("notes")).unwrap();
+    fs::write(path.join("notes").join("todo.txt"), "untracked").unwrap();
+
+    repo.command()
+        .args(["remove", "--force", "--yes", "feature/dirty"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Backed up uncommitted changes to refs/wtw/backup/feature/dirty/",
+        ))
+        .stdout(predicate::str::contains("Archived untracked files to"))
+        .stdout(predicate::str::contains(
+            "Restore with: wtw restore feature/dirty",
+        ));
+    assert!(!path.exists());
+
+    repo.command()
+        .arg("restore")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/dirty"))
+        .stdout(predicate::str::contains("refs/wtw/backup/feature/dirty/"));
+
+    repo.command()
+        .args(["restore", "feature/dirty", "--drop"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Restored worktree 'feature/dirty'",
+        ))
+        .stdout(predicate::str::contains("Dropped backup"));
+
+    assert_eq!(
+        fs::read_to_string(path.join("README.md")).unwrap(),
+        "edited"
+    );
+    assert_eq!(
+        fs::read_to_string(path.join("notes").join("todo.txt")).unwrap(),
+        "untracked"
+    );
+    assert_eq!(
+        git_stdout(&path, &["diff", "--cached", "--name-only"]),
+        "staged.txt"
+    );
+    assert_eq!(
+        git_stdout(&path, &["rev-parse", "--abbrev-ref", "HEAD"]),
+        "feature/dirty"
+    );
+
+    repo.command()
+        .arg("restore")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No backups found"));
+}
+
+#[test]
+fn restore_recreates_branch_deleted_with_the_worktree() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/gone");
+    repo.command()
+        .args(["add", "feature/gone"])
+        .assert()
+        .success();
+    let path = repo.worktree_path_for("feature/gone");
+    fs::write(path.join("only-untracked.txt"), "keep me").unwrap();
+
+    repo.command()
+        .args([
+            "remove",
+            "--force",
+            "--yes",
+            "--with-branch",
+            "--force-branch",
+            "feature/gone",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed branch 'feature/gone'"));
+
+    repo.command()
+        .args(["restore", "feature/gone"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(path.join("only-untracked.txt")).unwrap(),
+        "keep me"
+    );
+    assert_eq!(
+        git_stdout(&path, &["rev-parse", "--abbrev-ref", "HEAD"]),
+        "feature/gone"
+    );
+}
+
+#[test]
+fn forced_remove_of_clean_worktree_takes_no_backup() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/clean");
+    repo.command()
+        .args(["add", "feature/clean"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["remove", "--force", "feature/clean"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Backed up").not());
+
+    repo.command()
+        .args(["restore", "feature/clean"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "backup of 'feature/clean' not found",
+        ));
+}
+
+#[test]
+fn forced_remove_backs_up_worktree_whose_name_is_not_a_valid_ref() {
+    let repo = TestRepo::new();
+    let outside = tempfile::TempDir::new().unwrap();
+    let path = outside.path().join("my draft");
+    repo.git(&[
+        "worktree",
+        "add",
+        "-q",
+        "-b",
+        "draft",
+        path.to_str().unwrap(),
+    ]);
+    fs::write(path.join("README.md"), "edited").unwrap();
+
+    repo.command()
+        .args([
+            "remove",
+            "--force",
+            "--yes",
+            "--include-unmanaged",
+            "my draft",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Backed up uncommitted changes to refs/wtw/backup/my-draft/",
+        ))
+        .stdout(predicate::str::contains(
+            "Restore with: wtw restore my-draft",
+        ));
+    assert!(!path.exists());
+
+    repo.command()
+        .args(["restore", "my draft"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored worktree 'my-draft'"));
+    assert_eq!(
+        fs::read_to_string(path.join("README.md")).unwrap(),
+        "edited"
+    );
+}
+
+#[test]
+fn repeated_forced_removals_keep_every_backup() {
+    let repo = TestRepo::new();
+    repo.create_branch("feature/twice");
+    let path = repo.worktree_path_for("feature/twice");
+    // Back to back, so both backups usually fall within the same second.
+    for content in ["first", "second"] {
+        repo.command()
+            .args(["add", "feature/twice"])
+            .assert()
+            .success();
+        fs::write(path.join("README.md"), content).unwrap();
+        repo.command()
+            .args(["remove", "--force", "--yes", "feature/twice"])
+            .assert()
+            .success();
+    }
+
+    let refs = git_stdout(
+        repo.path(),
+        &[
+            "for-each-ref",
+            "--format=%(refname)",
+            "refs/wtw/backup/feature/twice/",
+        ],
+    );
+    assert_eq!(refs.lines().count(), 2, "{refs}");
+
+    // The newest backup is restored first.
+    repo.command()
+        .args(["restore", "feature/twice", "--drop"])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(path.join("README.md")).unwrap(),
+        "second"
+    );
+}
+
+fn git_stdout(dir: &Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("git");
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use std::fs;
+
+use common::{TestRepo, run_git};
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn status_json(repo: &TestRepo, args: &[&str]) -> Value {
+    let output = repo
+        .command()
+        .args(["status", "--json"])
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).unwrap()
+}
+
+/// `(worktree, kind)` pairs of one severity group.
+fn findings(report: &Value, severity: &str) -> Vec<(String, String)> {
+    report["findings"][severity]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            (
+                finding["worktree"].as_str().unwrap().to_string(),
+                finding["kind"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+fn has(findings: &[(String, String)], worktree: &str, kind: &str) -> bool {
+    findings
+        .iter()
+        .any(|(name, found)| name == worktree && found == kind)
+}
+
+#[test]
+fn status_groups_findings_by_severity() {
+    let repo = TestRepo::new();
+    let remote = TempDir::new().unwrap();
+    run_git(remote.path(), &["init", "-q", "--bare"]);
+    repo.git(&["remote", "add", "origin", remote.path().to_str().unwrap()]);
+
+    for branch in ["feature/work", "feature/old", "feature/gone"] {
+        repo.create_branch(branch);
+        repo.command().args(["add", branch]).assert().success();
+    }
+
+    // Unpushed commits, a stash and an untracked file in feature/work.
+    let work = repo.worktree_path_for("feature/work");
+    run_git(&work, &["push", "-q", "-u", "origin", "feature/work"]);
+    fs::write(work.join("note.txt"), "draft").unwrap();
+    run_git(&work, &["add", "note.txt"]);
+    run_git(&work, &["commit", "-q", "-m", "draft"]);
+    fs::write(work.join("scratch.txt"), "stash me").unwrap();
+    run_git(&work, &["stash", "push", "-q", "-u"]);
+    fs::write(work.join("todo.txt"), "later").unwrap();
+
+    // feature/old tracks a remote branch that has since been deleted.
+    let old = repo.worktree_path_for("feature/old");
+    run_git(&old, &["push", "-q", "-u", "origin", "feature/old"]);
+    repo.git(&["push", "-q", "origin", "--delete", "feature/old"]);
+    repo.git(&["fetch", "-q", "--prune", "origin"]);
+
+    fs::remove_dir_all(repo.worktree_path_for("feature/gone")).unwrap();
+
+    let report = status_json(&repo, &[]);
+    let sep = std::path::MAIN_SEPARATOR;
+    let work_name = format!("feature{sep}work");
+    let gone_name = format!("feature{sep}gone");
+    let old_name = format!("feature{sep}old");
+
+    let errors = findings(&report, "error");
+    assert_eq!(errors, [(gone_name.clone(), "missing".to_string())]);
+
+    let warnings = findings(&report, "warning");
+    assert!(has(&warnings, &work_name, "uncommitted"), "{warnings:?}");
+    assert!(has(&warnings, &work_name, "stashes"), "{warnings:?}");
+    assert!(has(&warnings, &work_name, "unpushed"), "{warnings:?}");
+    assert!(has(&warnings, &old_name, "upstream-gone"), "{warnings:?}");
+    assert!(!has(&warnings, &work_name, "untouched"), "{warnings:?}");
+
+    let info = findings(&report, "info");
+    assert!(has(&info, &gone_name, "no-upstream"), "{info:?}");
+
+    let worktrees = report["worktrees"].as_array().unwrap();
+    assert_eq!(worktrees.len(), 4);
+    let work_entry = worktrees
+        .iter()
+        .find(|entry| entry["name"] == work_name.as_str())
+        .unwrap();
+    assert!(work_entry["disk_bytes"].as_u64().unwrap() > 0);
+    assert!(work_entry["last_touched"].is_u64());
+    let gone_entry = worktrees
+        .iter()
+        .find(|entry| entry["name"] == gone_name.as_str())
+        .unwrap();
+    assert!(gone_entry["disk_bytes"].is_null());
+    assert!(report["disk_bytes"].as_u64().unwrap() > 0);
+
+    let no_disk = status_json(&repo, &["--no-disk"]);
+    assert!(no_disk["worktrees"][1]["disk_bytes"].is_null());
+
+    repo.command()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Errors (1)"))
+        .stdout(predicate::str::contains("Warnings ("))
+        .stdout(predicate::str::contains(
+            "1 commit not pushed to 'origin/feature/work'",
+        ))
+        .stdout(predicate::str::contains(
+            "1 stash made on a branch named 'feature/work'",
+        ))
+        .stdout(predicate::str::contains(
+            "upstream 'origin/feature/old' no longer exists",
+        ));
+}
+
+#[test]
+fn status_reports_healthy_worktrees() {
+    let repo = TestRepo::new();
+    // The base directory lives inside the main worktree; ignore it so the
+    // main worktree is clean.
+    fs::write(repo.path().join(".gitignore"), "worktree/\n").unwrap();
+    repo.git(&["add", ".gitignore"]);
+    repo.git(&["commit", "-q", "-m", "ignore worktrees"]);
+
+    repo.command()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found in 1 worktree"));
+}